@@ -0,0 +1,260 @@
+//! Bridges between Observables and [`std::sync::mpsc`] channels.
+//!
+//! [`from_receiver`] creates an Observable emitting the values received over a
+//! channel, and a [`SenderObserver`] sends the values it observes into one.
+use crate::prelude::*;
+use crate::scheduler::Scheduler;
+use std::sync::Mutex;
+use std::sync::mpsc::{
+  Receiver, RecvTimeoutError, SendError, Sender, SyncSender,
+};
+use std::time::Duration;
+
+/// How long a receiving task blocks at most before it checks whether it was
+/// unsubscribed from.
+const RECV_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Creates an Observable that emits the values received by `receiver`, and
+/// completes once all of its senders are dropped.
+///
+/// The receiver is drained by a task on `scheduler`, which holds the thread it
+/// runs on until the subscription ends, as receiving blocks. With a
+/// `Schedulers::ThreadPool`, every subscription takes a pool thread for that
+/// long, so a `Schedulers::NewThread` is the better fit. The task checks the
+/// subscription every few milliseconds, and stops soon after unsubscribing
+/// even if no value arrives. As the receiver can't be cloned, the Observable
+/// can only be subscribed to once.
+///
+/// Waiting on a [`futures::channel::mpsc`] receiver doesn't block a thread, so
+/// where the channel can be chosen, prefer one of those and pass its receiver
+/// to [`from_stream`](crate::observable::from_stream):
+///
+/// ```
+/// use futures::{channel::mpsc, executor::ThreadPool};
+/// use rxrust::prelude::*;
+///
+/// let (tx, rx) = mpsc::unbounded();
+/// let sum = observable::from_stream(rx, ThreadPool::new().unwrap()).sum();
+/// for i in 1..4 {
+///   tx.unbounded_send(i).unwrap();
+/// }
+/// drop(tx);
+/// assert_eq!(sum.blocking_last(None), Ok(6));
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// let sum = observable::from_receiver(rx, Schedulers::NewThread).sum();
+/// for i in 1..4 {
+///   tx.send(i).unwrap();
+/// }
+/// drop(tx);
+/// assert_eq!(sum.blocking_last(None), Ok(6));
+/// ```
+pub fn from_receiver<Item, SD>(
+  receiver: Receiver<Item>,
+  scheduler: SD,
+) -> ObservableBase<ReceiverEmitter<Item, SD>>
+where
+  Item: Send + 'static,
+  SD: Scheduler,
+{
+  ObservableBase::new(ReceiverEmitter {
+    receiver,
+    scheduler,
+  })
+}
+
+pub struct ReceiverEmitter<Item, SD> {
+  receiver: Receiver<Item>,
+  scheduler: SD,
+}
+
+impl<Item, SD> Emitter for ReceiverEmitter<Item, SD> {
+  type Item = Item;
+  type Err = ();
+}
+
+impl<Item, SD> SharedEmitter for ReceiverEmitter<Item, SD>
+where
+  Item: Send + 'static,
+  SD: Scheduler,
+{
+  fn emit<O>(self, subscriber: Subscriber<O, SharedSubscription>)
+  where
+    O: Observer<Self::Item, Self::Err> + Send + Sync + 'static,
+  {
+    let mut subscription = subscriber.subscription.clone();
+    let handle = self.scheduler.schedule(
+      move |_, (receiver, mut subscriber)| {
+        while !subscriber.is_closed() {
+          match receiver.recv_timeout(RECV_POLL_INTERVAL) {
+            Ok(v) => subscriber.next(v),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => subscriber.complete(),
+          }
+        }
+      },
+      None,
+      (self.receiver, subscriber),
+    );
+    subscription.add(handle);
+  }
+}
+
+/// The sending half of a [`std::sync::mpsc`] channel, which is either a
+/// [`Sender`] or a [`SyncSender`].
+pub trait ChannelSender<Item> {
+  fn send(&self, value: Item) -> Result<(), SendError<Item>>;
+}
+
+impl<Item> ChannelSender<Item> for Sender<Item> {
+  #[inline]
+  fn send(&self, value: Item) -> Result<(), SendError<Item>> {
+    Sender::send(self, value)
+  }
+}
+
+impl<Item> ChannelSender<Item> for SyncSender<Item> {
+  #[inline]
+  fn send(&self, value: Item) -> Result<(), SendError<Item>> {
+    SyncSender::send(self, value)
+  }
+}
+
+/// An Observer sending the values it observes into a [`Sender`] or
+/// [`SyncSender`].
+///
+/// The sender is dropped once the Observable completes or emits an error,
+/// which closes the channel if it was the last sender. A [`SyncSender`] blocks
+/// the Observable while the channel is full. If the receiver is dropped, all
+/// further values are dropped too.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::{channel::SenderObserver, prelude::*};
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// observable::from_iter(0..3)
+///   .to_shared()
+///   .actual_subscribe(Subscriber::shared(SenderObserver::new(tx)));
+/// assert_eq!(rx.iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+/// ```
+pub struct SenderObserver<S> {
+  // A `Sender` isn't `Sync`, the lock makes the observer shareable across
+  // threads. It's never contended, as observing takes `&mut self`.
+  sender: Mutex<Option<S>>,
+}
+
+impl<S> SenderObserver<S> {
+  pub fn new(sender: S) -> Self {
+    SenderObserver {
+      sender: Mutex::new(Some(sender)),
+    }
+  }
+
+  fn close(&mut self) { self.sender.get_mut().unwrap().take(); }
+}
+
+impl<Item, Err, S> Observer<Item, Err> for SenderObserver<S>
+where
+  S: ChannelSender<Item>,
+{
+  fn next(&mut self, value: Item) {
+    let sender = self.sender.get_mut().unwrap();
+    if let Some(s) = sender {
+      if s.send(value).is_err() {
+        sender.take();
+      }
+    }
+  }
+
+  fn error(&mut self, _err: Err) { self.close() }
+
+  fn complete(&mut self) { self.close() }
+}
+
+#[cfg(test)]
+mod test {
+  use super::SenderObserver;
+  use crate::prelude::*;
+  use std::sync::mpsc;
+  use std::sync::{Arc, Mutex};
+  use std::thread;
+
+  #[test]
+  fn receive_until_disconnected() {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+      for i in 0..3 {
+        tx.send(i).unwrap();
+      }
+    });
+    let values = observable::from_receiver(rx, Schedulers::NewThread)
+      .to_vec()
+      .blocking_last(None);
+    assert_eq!(values, Ok(vec![0, 1, 2]));
+  }
+
+  #[test]
+  fn unsubscribe_stops_receiving() {
+    let (tx, rx) = mpsc::channel();
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    let mut subscription = observable::from_receiver(rx, Schedulers::NewThread)
+      .to_shared()
+      .subscribe(move |v| c_values.lock().unwrap().push(v));
+    tx.send(1).unwrap();
+    while values.lock().unwrap().is_empty() {
+      thread::yield_now();
+    }
+    subscription.unsubscribe();
+    // Once the receiving task ends, the receiver is dropped.
+    while tx.send(2).is_ok() {
+      thread::yield_now();
+    }
+    assert_eq!(*values.lock().unwrap(), vec![1]);
+  }
+
+  #[test]
+  fn sender_observer() {
+    let (tx, rx) = mpsc::channel();
+    let mut subject: SharedSubject<i32, ()> = SharedSubject::new();
+    subject
+      .clone()
+      .actual_subscribe(Subscriber::shared(SenderObserver::new(tx)));
+    subject.next(1);
+    subject.next(2);
+    subject.complete();
+    assert_eq!(rx.iter().collect::<Vec<_>>(), vec![1, 2]);
+  }
+
+  #[test]
+  fn sync_sender_observer() {
+    let (tx, rx) = mpsc::sync_channel(0);
+    let consumer = thread::spawn(move || rx.iter().collect::<Vec<_>>());
+    observable::from_iter(0..5)
+      .to_shared()
+      .actual_subscribe(Subscriber::shared(SenderObserver::new(tx)));
+    assert_eq!(consumer.join().unwrap(), vec![0, 1, 2, 3, 4]);
+  }
+
+  #[test]
+  fn sender_observer_closes_on_error() {
+    let (tx, rx) = mpsc::channel();
+    let mut subject = SharedSubject::new();
+    subject
+      .clone()
+      .actual_subscribe(Subscriber::shared(SenderObserver::new(tx)));
+    subject.next(1);
+    subject.error("oops");
+    assert_eq!(rx.iter().collect::<Vec<_>>(), vec![1]);
+  }
+}
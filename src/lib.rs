@@ -10,11 +10,13 @@ extern crate lazy_static;
 #[cfg(test)]
 extern crate float_cmp;
 
+pub mod channel;
 pub mod observable;
 pub mod observer;
 pub mod ops;
 pub mod scheduler;
 pub mod shared;
+pub mod sink;
 pub mod subject;
 pub mod subscriber;
 pub mod subscription;
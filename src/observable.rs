@@ -10,8 +10,14 @@ pub use of::{of, of_fn, of_option, of_result, of_sequence};
 pub(crate) mod from_future;
 pub use from_future::{from_future, from_future_result};
 
+pub mod from_stream;
+pub use from_stream::{from_stream, try_from_stream};
+
 pub(crate) mod interval;
-pub use interval::{interval, interval_at};
+pub use interval::{interval, interval_at, local_interval, local_interval_at};
+
+pub mod timer;
+pub use timer::{timer, timer_at, timer_periodic};
 
 pub(crate) mod connectable_observable;
 pub use connectable_observable::{
@@ -25,6 +31,23 @@ pub use base::*;
 pub mod from_fn;
 pub use from_fn::*;
 
+pub mod race;
+pub use race::race;
+
+pub mod fork_join;
+pub use fork_join::fork_join;
+
+pub mod defer;
+pub use defer::defer;
+
+pub mod into_stream;
+
+pub mod blocking;
+
+pub use crate::channel::from_receiver;
+
+pub mod to_future;
+
 mod observable_all;
 pub use observable_all::*;
 mod observable_err;
@@ -37,32 +60,68 @@ pub use observable_comp::*;
 
 use crate::ops::default_if_empty::DefaultIfEmptyOp;
 use ops::{
+  all::{AllOp, IsEmptyOp},
+  any::{AnyOp, ContainsOp},
+  collect::{CollectOp, ToHashMap},
   box_it::{BoxOp, IntoBox},
-  delay::DelayOp,
+  delay::{DelayOp, LocalDelayOp},
+  distinct::{DistinctFlushOp, DistinctOp},
+  distinct_until_changed::{
+    DistinctUntilChangedEqOp, DistinctUntilChangedOp, DistinctUntilKeyChangedOp,
+  },
+  end_with::EndWithOp,
   filter::FilterOp,
   finalize::FinalizeOp,
+  find_index::FindIndexOp,
   first::FirstOrOp,
   last::LastOrOp,
   map::MapOp,
   map_to::MapToOp,
   merge::MergeOp,
   observe_on::ObserveOnOp,
+  pairwise::PairwiseOp,
+  reduce_by::{ByKey, CountBy, MinMaxBy, ReduceByOp, ToMapBy},
+  partition::{Partition, PartitionOp},
   ref_count::{RefCount, RefCountCreator},
   sample::SampleOp,
   scan::ScanOp,
+  sequence_equal::SequenceEqualOp,
+  single::SingleOp,
   skip::SkipOp,
   skip_last::SkipLastOp,
+  skip_until::SkipUntilOp,
+  skip_while::SkipWhileOp,
+  sliding::SlidingOp,
+  start_with::StartWithOp,
+  statistics::{
+    Mean, Numeric, Percentile, Statistic, StatisticOp, StdDev, Variance, Window,
+  },
   subscribe_on::SubscribeOnOP,
   take::TakeOp,
   take_last::TakeLastOp,
   take_until::TakeUntilOp,
   take_while::TakeWhileOp,
-  throttle_time::{ThrottleEdge, ThrottleTimeOp},
+  tap::{
+    OnSubscribeOp, OnUnsubscribeOp, TapCompleteOp, TapErrorOp, TapNextOp, TapOp,
+  },
+  throttle_time::{LocalThrottleTimeOp, ThrottleEdge, ThrottleTimeOp},
+  time_interval::TimeIntervalOp,
+  timestamp::TimestampOp,
   zip::ZipOp,
-  Accum, AverageOp, CountOp, MinMaxOp, ReduceOp, SumOp,
+  Accum, AverageOp, CountByOp, CountOp, IgnoreElementsOp, MinMaxByOp, MinMaxOp,
+  ReduceOp, SumOp, ToHashMapOp, ToMapByOp,
 };
+use fork_join::ForkJoinWithOp;
+use futures::task::LocalSpawn;
+use into_stream::{LocalObservableStream, ObservableStream, StreamState};
+use race::RaceWithOp;
+use to_future::{LocalObservableFuture, ObservableFuture};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::ops::{Add, Mul};
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
 pub trait Observable {
@@ -90,6 +149,61 @@ pub trait Observable {
     }
   }
 
+  /// Emits only the first item emitted by an Observable that satisfies the
+  /// `predicate`, then completes.
+  ///
+  /// An alias of [`find`](Observable::find), named after
+  /// [`first`](Observable::first).
+  #[inline]
+  fn first_where<F>(self, predicate: F) -> TakeOp<FilterOp<Self, F>>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Item) -> bool,
+  {
+    self.find(predicate)
+  }
+
+  /// Emits only the item at `index` (counted from 0) emitted by an
+  /// Observable, then completes.
+  ///
+  /// Completes without emitting if the source completes before reaching
+  /// `index`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec!['a', 'b', 'c'])
+  ///   .element_at(1)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // b
+  /// ```
+  #[inline]
+  fn element_at(self, index: u32) -> TakeOp<SkipOp<Self>>
+  where
+    Self: Sized,
+  {
+    self.skip(index).first()
+  }
+
+  /// Emits only the item at `index` (counted from 0) emitted by an
+  /// Observable, or `default` if the source completes before reaching
+  /// `index`.
+  #[inline]
+  fn element_at_or(
+    self,
+    index: u32,
+    default: Self::Item,
+  ) -> FirstOrOp<TakeOp<SkipOp<Self>>, Self::Item>
+  where
+    Self: Sized,
+  {
+    self.skip(index).first_or(default)
+  }
+
   /// Emit only the last final item emitted by a source observable or a
   /// default item given.
   ///
@@ -149,193 +263,931 @@ pub trait Observable {
     }
   }
 
-  /// Call a function when observable completes, errors or is unsubscribed from.
+  /// Emits whether every value emitted by the source Observable satisfies
+  /// the `predicate`.
+  ///
+  /// Emits `false` and completes as soon as a value fails the `predicate`,
+  /// unsubscribing from the source. Emits `true` when the source completes
+  /// otherwise, so an empty source results in `true`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .all(|v| v % 2 == 0)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // false
+  /// ```
   #[inline]
-  fn finalize<F>(self, f: F) -> FinalizeOp<Self, F>
+  fn all<F>(self, predicate: F) -> AllOp<Self, F>
   where
     Self: Sized,
-    F: FnMut(),
+    F: FnMut(&Self::Item) -> bool,
   {
-    FinalizeOp {
+    AllOp {
       source: self,
-      func: f,
+      predicate,
     }
   }
 
-  /// Creates a new stream which calls a closure on each element and uses
-  /// its return as the value.
+  /// Emits whether any value emitted by the source Observable satisfies the
+  /// `predicate`.
+  ///
+  /// Emits `true` and completes as soon as a value satisfies the
+  /// `predicate`, unsubscribing from the source. Emits `false` when the
+  /// source completes otherwise.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .any(|v| *v > 5)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // true
+  /// ```
   #[inline]
-  fn map<B, F>(self, f: F) -> MapOp<Self, F>
+  fn any<F>(self, predicate: F) -> AnyOp<Self, F>
   where
     Self: Sized,
-    F: Fn(Self::Item) -> B,
+    F: FnMut(&Self::Item) -> bool,
   {
-    MapOp {
+    AnyOp {
       source: self,
-      func: f,
+      predicate,
     }
   }
 
-  /// Maps emissions to a constant value.
+  /// Emits whether the source Observable emits a value equal to `item`.
+  ///
+  /// Like [`any`](Observable::any), it emits `true` and unsubscribes from the
+  /// source as soon as a matching value arrives.
   #[inline]
-  fn map_to<B>(self, value: B) -> MapToOp<Self, B>
+  fn contains(self, item: Self::Item) -> ContainsOp<Self, Self::Item>
   where
     Self: Sized,
+    Self::Item: PartialEq,
   {
-    MapToOp {
+    ContainsOp { source: self, item }
+  }
+
+  /// Emits `true` if the source Observable completes without emitting any
+  /// value, and `false` as soon as it emits one.
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn is_empty(self) -> IsEmptyOp<Self, Self::Item>
+  where
+    Self: Sized,
+  {
+    AllOp {
       source: self,
-      value,
+      predicate: |_| false,
     }
   }
 
-  /// combine two Observables into one by merging their emissions
+  /// Emits only the first value emitted by the source Observable that
+  /// satisfies the `predicate`, then completes.
+  ///
+  /// If no value satisfies the `predicate`, completes without emitting.
   ///
   /// # Example
   ///
   /// ```
-  /// # use rxrust::prelude::*;
-  /// let numbers = Subject::new();
-  /// // crate a even stream by filter
-  /// let even = numbers.clone().filter(|v| *v % 2 == 0);
-  /// // crate an odd stream by filter
-  /// let odd = numbers.clone().filter(|v| *v % 2 != 0);
+  /// use rxrust::prelude::*;
   ///
-  /// // merge odd and even stream again
-  /// let merged = even.merge(odd);
+  /// observable::from_iter(vec!["a", "bb", "ccc"])
+  ///   .find(|v| v.len() > 1)
+  ///   .subscribe(|v| println!("{}", v));
   ///
-  /// // attach observers
-  /// merged.subscribe(|v: &i32| println!("{} ", v));
+  /// // print log:
+  /// // bb
   /// ```
   #[inline]
-  fn merge<S>(self, o: S) -> MergeOp<Self, S>
+  fn find<F>(self, predicate: F) -> TakeOp<FilterOp<Self, F>>
   where
     Self: Sized,
-    S: Observable<Item = Self::Item, Err = Self::Err>,
+    F: FnMut(&Self::Item) -> bool,
   {
-    MergeOp {
-      source1: self,
-      source2: o,
+    FilterOp {
+      source: self,
+      filter: predicate,
     }
+    .first()
   }
 
-  /// Emit only those items from an Observable that pass a predicate test
+  /// Emits the index of the first value emitted by the source Observable
+  /// that satisfies the `predicate`, then completes.
+  ///
+  /// If no value satisfies the `predicate`, completes without emitting.
+  ///
   /// # Example
   ///
   /// ```
-  /// use rxrust:: prelude::*;
+  /// use rxrust::prelude::*;
   ///
-  /// let mut coll = vec![];
-  /// let coll_clone = coll.clone();
+  /// observable::from_iter(vec!["a", "bb", "ccc"])
+  ///   .find_index(|v| v.len() > 1)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 1
+  /// ```
+  #[inline]
+  fn find_index<F>(self, predicate: F) -> FindIndexOp<Self, F>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Item) -> bool,
+  {
+    FindIndexOp {
+      source: self,
+      predicate,
+    }
+  }
+
+  /// Emits whether the source Observable and `other` emit the same sequence
+  /// of values.
+  ///
+  /// Both Observables are subscribed to at once and compared value by value.
+  /// As soon as they differ, `false` is emitted and both are unsubscribed
+  /// from. `true` is emitted once both completed with the same values.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .sequence_equal(observable::from_iter(vec![0, 1, 2]))
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // true
+  /// ```
+  #[inline]
+  fn sequence_equal<S>(self, other: S) -> SequenceEqualOp<Self, S>
+  where
+    Self: Sized,
+    Self::Item: PartialEq,
+    S: Observable<Item = Self::Item, Err = Self::Err>,
+  {
+    SequenceEqualOp { a: self, b: other }
+  }
+
+  /// Emits only the last item emitted by a source observable that satisfies
+  /// the `predicate`.
+  ///
+  /// Completes without emitting if no item satisfied the `predicate`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
   ///
   /// observable::from_iter(0..10)
-  ///   .filter(|v| *v % 2 == 0)
-  ///   .subscribe(|v| { coll.push(v); });
+  ///   .last_where(|v| v % 4 == 3)
+  ///   .subscribe(|v| println!("{}", v));
   ///
-  /// // only even numbers received.
-  /// assert_eq!(coll, vec![0, 2, 4, 6, 8]);
+  /// // print log:
+  /// // 7
   /// ```
   #[inline]
-  fn filter<F>(self, filter: F) -> FilterOp<Self, F>
+  fn last_where<F>(
+    self,
+    predicate: F,
+  ) -> LastOrOp<FilterOp<Self, F>, Self::Item>
   where
     Self: Sized,
-    F: Fn(&Self::Item) -> bool,
+    F: FnMut(&Self::Item) -> bool,
   {
     FilterOp {
       source: self,
-      filter,
+      filter: predicate,
+    }
+    .last()
+  }
+
+  /// Emits the only item emitted by a source observable.
+  ///
+  /// Errors with [`SingleError::TooMany`](ops::single::SingleError::TooMany)
+  /// as soon as a second item arrives, unsubscribing from the source, and
+  /// with [`SingleError::Empty`](ops::single::SingleError::Empty) if the
+  /// source completes without any item. Errors of the source are wrapped in
+  /// [`SingleError::Source`](ops::single::SingleError::Source).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use rxrust::ops::single::SingleError;
+  ///
+  /// observable::from_iter(0..2)
+  ///   .single()
+  ///   .subscribe_err(
+  ///     |v| println!("{}", v),
+  ///     |e| assert_eq!(e, SingleError::TooMany),
+  ///   );
+  /// ```
+  #[inline]
+  fn single(self) -> SingleOp<Self, Self::Item>
+  where
+    Self: Sized,
+  {
+    SingleOp {
+      source: self,
+      default: None,
+    }
+  }
+
+  /// Emits the only item emitted by a source observable, or `default` if
+  /// the source completes without any item.
+  ///
+  /// Still errors with
+  /// [`SingleError::TooMany`](ops::single::SingleError::TooMany) if the
+  /// source emits more than one item.
+  #[inline]
+  fn single_or_default(self, default: Self::Item) -> SingleOp<Self, Self::Item>
+  where
+    Self: Sized,
+  {
+    SingleOp {
+      source: self,
+      default: Some(default),
+    }
+  }
+
+  /// Ignores all items emitted by the source observable and only mirrors its
+  /// termination, either complete or error.
+  #[inline]
+  fn ignore_elements(self) -> IgnoreElementsOp<Self, Self::Item>
+  where
+    Self: Sized,
+  {
+    FilterOp {
+      source: self,
+      filter: |_| false,
+    }
+  }
+
+  /// Call a function when observable completes, errors or is unsubscribed from.
+  #[inline]
+  fn finalize<F>(self, f: F) -> FinalizeOp<Self, F>
+  where
+    Self: Sized,
+    F: FnMut(),
+  {
+    FinalizeOp {
+      source: self,
+      func: f,
+    }
+  }
+
+  /// Perform a side effect for every notification of the source observable,
+  /// but return an observable that is identical to the source.
+  ///
+  /// The `observer` receives references to the values and errors, so the
+  /// items don't need to be `Clone`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .tap(ObserverAll::new(
+  ///     |v: &i32| println!("next {}", v),
+  ///     |_: &()| println!("error"),
+  ///     || println!("complete"),
+  ///   ))
+  ///   .subscribe(|_| {});
+  /// ```
+  #[inline]
+  fn tap<T>(self, observer: T) -> TapOp<Self, T>
+  where
+    Self: Sized,
+    T: for<'r> Observer<&'r Self::Item, &'r Self::Err>,
+  {
+    TapOp {
+      source: self,
+      tap: observer,
     }
   }
 
-  /// box an observable to a safety object and convert it to a simple type
-  /// `BoxOp`, which only care `Item` and `Err` Observable emitted.
+  /// Call a function with a reference to every value the source observable
+  /// emits, without changing the stream.
+  #[inline]
+  fn tap_next<F>(
+    self,
+    f: F,
+  ) -> TapNextOp<Self, Self::Err, F>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Item),
+  {
+    self.tap(ObserverAll::new(f, noop_tap_error, noop_tap_complete))
+  }
+
+  /// Call a function with a reference to the error of the source observable,
+  /// without changing the stream.
+  #[inline]
+  fn tap_error<F>(
+    self,
+    f: F,
+  ) -> TapErrorOp<Self, Self::Item, F>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Err),
+  {
+    self.tap(ObserverAll::new(noop_tap_next, f, noop_tap_complete))
+  }
+
+  /// Call a function when the source observable completes, without changing
+  /// the stream.
+  #[inline]
+  fn tap_complete<F>(
+    self,
+    f: F,
+  ) -> TapCompleteOp<Self, Self::Item, Self::Err, F>
+  where
+    Self: Sized,
+    F: FnMut(),
+  {
+    self.tap(ObserverAll::new(noop_tap_next, noop_tap_error, f))
+  }
+
+  /// Call a function every time the observable is subscribed to, right
+  /// before the subscription to the source is made.
+  #[inline]
+  fn on_subscribe<F>(self, f: F) -> OnSubscribeOp<Self, F>
+  where
+    Self: Sized,
+    F: FnMut(),
+  {
+    OnSubscribeOp {
+      source: self,
+      func: f,
+    }
+  }
+
+  /// Call a function when the subscription is torn down by
+  /// [`unsubscribe`](SubscriptionLike::unsubscribe) before the source
+  /// completed or emitted an error.
+  ///
+  /// Unlike [`finalize`](Observable::finalize), the function is not called
+  /// when the source terminates by itself.
+  #[inline]
+  fn on_unsubscribe<F>(self, f: F) -> OnUnsubscribeOp<Self, F>
+  where
+    Self: Sized,
+    F: FnMut(),
+  {
+    OnUnsubscribeOp {
+      source: self,
+      func: f,
+    }
+  }
+
+  /// Creates a new stream which calls a closure on each element and uses
+  /// its return as the value.
+  #[inline]
+  fn map<B, F>(self, f: F) -> MapOp<Self, F>
+  where
+    Self: Sized,
+    F: Fn(Self::Item) -> B,
+  {
+    MapOp {
+      source: self,
+      func: f,
+    }
+  }
+
+  /// Maps emissions to a constant value.
+  #[inline]
+  fn map_to<B>(self, value: B) -> MapToOp<Self, B>
+  where
+    Self: Sized,
+  {
+    MapToOp {
+      source: self,
+      value,
+    }
+  }
+
+  /// combine two Observables into one by merging their emissions
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// let numbers = Subject::new();
+  /// // crate a even stream by filter
+  /// let even = numbers.clone().filter(|v| *v % 2 == 0);
+  /// // crate an odd stream by filter
+  /// let odd = numbers.clone().filter(|v| *v % 2 != 0);
+  ///
+  /// // merge odd and even stream again
+  /// let merged = even.merge(odd);
+  ///
+  /// // attach observers
+  /// merged.subscribe(|v: &i32| println!("{} ", v));
+  /// ```
+  #[inline]
+  fn merge<S>(self, o: S) -> MergeOp<Self, S>
+  where
+    Self: Sized,
+    S: Observable<Item = Self::Item, Err = Self::Err>,
+  {
+    MergeOp {
+      source1: self,
+      source2: o,
+    }
+  }
+
+  /// Mirrors whichever of this Observable and `other` notifies first, and
+  /// unsubscribes from the other one.
+  ///
+  /// See [`observable::race`](race) for racing more than two Observables of
+  /// the same type.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// let mut primary = Subject::new();
+  /// let mut fallback = Subject::new();
+  /// primary
+  ///   .clone()
+  ///   .race_with(fallback.clone())
+  ///   .subscribe(|v: &str| println!("{}", v));
+  ///
+  /// fallback.next("fallback");
+  /// primary.next("primary");
+  ///
+  /// // print log:
+  /// // fallback
+  /// ```
+  #[inline]
+  fn race_with<S>(self, other: S) -> RaceWithOp<Self, S>
+  where
+    Self: Sized,
+    S: Observable<Item = Self::Item, Err = Self::Err>,
+  {
+    RaceWithOp { a: self, b: other }
+  }
+
+  /// Waits for this Observable and `other` to complete, then emits a tuple
+  /// of their last values.
+  ///
+  /// Errors and cancels the other one as soon as either of them errors, and
+  /// completes without emitting as soon as either of them completes without
+  /// emitting a value. See
+  /// [`observable::fork_join`](fork_join) for joining more than two
+  /// Observables of the same type.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// observable::from_iter(0..3)
+  ///   .fork_join_with(observable::of("done"))
+  ///   .subscribe(|v| println!("{:?}", v));
+  ///
+  /// // print log:
+  /// // (2, "done")
+  /// ```
+  #[inline]
+  fn fork_join_with<S>(
+    self,
+    other: S,
+  ) -> ForkJoinWithOp<Self, S>
+  where
+    Self: Sized,
+    S: Observable<Err = Self::Err>,
+  {
+    ForkJoinWithOp { a: self, b: other }
+  }
+
+  /// Emit only those items from an Observable that pass a predicate test
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust:: prelude::*;
+  ///
+  /// let mut coll = vec![];
+  /// let coll_clone = coll.clone();
+  ///
+  /// observable::from_iter(0..10)
+  ///   .filter(|v| *v % 2 == 0)
+  ///   .subscribe(|v| { coll.push(v); });
+  ///
+  /// // only even numbers received.
+  /// assert_eq!(coll, vec![0, 2, 4, 6, 8]);
+  /// ```
+  #[inline]
+  fn filter<F>(self, filter: F) -> FilterOp<Self, F>
+  where
+    Self: Sized,
+    F: Fn(&Self::Item) -> bool,
+  {
+    FilterOp {
+      source: self,
+      filter,
+    }
+  }
+
+  /// Emits all items emitted by the source Observable that are distinct by
+  /// comparison from previous items.
+  ///
+  /// Every emitted item is kept in a `HashSet` for the whole lifetime of the
+  /// subscription. Use [`distinct_with_flush`](Observable::distinct_with_flush)
+  /// to bound the memory used for long living sources.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut emitted = vec![];
+  /// observable::from_iter(vec![1, 2, 1, 3, 2])
+  ///   .distinct()
+  ///   .subscribe(|v| emitted.push(v));
+  ///
+  /// assert_eq!(emitted, vec![1, 2, 3]);
+  /// ```
+  #[inline]
+  fn distinct(self) -> DistinctOp<Self>
+  where
+    Self: Sized,
+    Self::Item: Hash + Eq + Clone,
+  {
+    DistinctOp { source: self }
+  }
+
+  /// Works like [`distinct`](Observable::distinct), but forgets all items
+  /// seen so far every time the `flushes` Observable emits.
+  #[inline]
+  fn distinct_with_flush<N>(self, flushes: N) -> DistinctFlushOp<Self, N>
+  where
+    Self: Sized,
+    Self::Item: Hash + Eq + Clone,
+    N: Observable<Err = Self::Err>,
+  {
+    DistinctFlushOp {
+      source: self,
+      flushes,
+    }
+  }
+
+  /// Emits all items emitted by the source Observable that are distinct by
+  /// comparison from the previous item.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut emitted = vec![];
+  /// observable::from_iter(vec![1, 1, 2, 2, 1])
+  ///   .distinct_until_changed()
+  ///   .subscribe(|v| emitted.push(v));
+  ///
+  /// assert_eq!(emitted, vec![1, 2, 1]);
+  /// ```
+  #[inline]
+  fn distinct_until_changed(
+    self,
+  ) -> DistinctUntilChangedEqOp<Self, Self::Item>
+  where
+    Self: Sized,
+    Self::Item: PartialEq + Clone,
+  {
+    self.distinct_until_changed_by(PartialEq::eq)
+  }
+
+  /// Works like [`distinct_until_changed`](Observable::distinct_until_changed)
+  /// but uses `compare` to decide whether an item is equal to the previous
+  /// one. `compare` is called with the previous and the current item.
+  #[inline]
+  fn distinct_until_changed_by<F>(
+    self,
+    compare: F,
+  ) -> DistinctUntilChangedOp<Self, F>
+  where
+    Self: Sized,
+    Self::Item: Clone,
+    F: FnMut(&Self::Item, &Self::Item) -> bool,
+  {
+    DistinctUntilChangedOp {
+      source: self,
+      compare,
+    }
+  }
+
+  /// Emits all items emitted by the source Observable whose key, computed by
+  /// `key`, is distinct from the key of the previous item.
+  #[inline]
+  fn distinct_until_key_changed<F, K>(
+    self,
+    key: F,
+  ) -> DistinctUntilKeyChangedOp<Self, F>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Item) -> K,
+    K: PartialEq,
+  {
+    DistinctUntilKeyChangedOp { source: self, key }
+  }
+
+  /// box an observable to a safety object and convert it to a simple type
+  /// `BoxOp`, which only care `Item` and `Err` Observable emitted.
+  ///
+  /// # Example
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use ops::box_it::LocalBoxOp;
+  ///
+  /// let mut boxed: LocalBoxOp<'_, i32, ()> = observable::of(1)
+  ///   .map(|v| v).box_it();
+  ///
+  /// // BoxOp can box any observable type
+  /// boxed = observable::empty().box_it();
+  ///
+  /// boxed.subscribe(|_| {});
+  /// ```
+  #[inline]
+  fn box_it<O: IntoBox<Self>>(self) -> BoxOp<O>
+  where
+    Self: Sized,
+    BoxOp<O>: Observable<Item = Self::Item, Err = Self::Err>,
+  {
+    O::box_it(self)
+  }
+
+  /// Ignore the first `count` values emitted by the source Observable.
+  ///
+  /// `skip` returns an Observable that ignore the first `count` values
+  /// emitted by the source Observable. If the source emits fewer than `count`
+  /// values then 0 of its values are emitted. After that, it completes,
+  /// regardless if the source completes.
+  ///
+  /// # Example
+  /// Ignore the first 5 seconds of an infinite 1-second interval Observable
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10).skip(5).subscribe(|v| println!("{}", v));
+
+  /// // print logs:
+  /// // 6
+  /// // 7
+  /// // 8
+  /// // 9
+  /// // 10
+  /// ```
+  #[inline]
+  fn skip(self, count: u32) -> SkipOp<Self>
+  where
+    Self: Sized,
+  {
+    SkipOp {
+      source: self,
+      count,
+    }
+  }
+
+  /// Ignore the last `count` values emitted by the source Observable.
+  ///
+  /// `skip_last` returns an Observable that ignore the last `count` values
+  /// emitted by the source Observable. If the source emits fewer than `count`
+  /// values then 0 of its values are emitted.
+  /// It will not emit values until source Observable complete.
+  ///
+  /// # Example
+  /// Skip the last 5 seconds of an infinite 1-second interval Observable
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .skip_last(5)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print logs:
+  /// // 0
+  /// // 1
+  /// // 2
+  /// // 3
+  /// // 4
+  /// ```
+  #[inline]
+  fn skip_last(self, count: usize) -> SkipLastOp<Self>
+  where
+    Self: Sized,
+  {
+    SkipLastOp {
+      source: self,
+      count,
+    }
+  }
+
+  /// Discards the values emitted by the source Observable until a `notifier`
+  /// Observable emits a value.
+  ///
+  /// `skip_until` subscribes to the `notifier` and begins mirroring the
+  /// source Observable as soon as the `notifier` emits its first value. The
+  /// `notifier` is unsubscribed from at that moment. If the `notifier`
+  /// completes without emitting, no value of the source is ever emitted.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// let mut emitted = vec![];
+  /// {
+  ///   let mut source = Subject::new();
+  ///   let mut notifier = Subject::new();
+  ///   source
+  ///     .clone()
+  ///     .skip_until(notifier.clone())
+  ///     .subscribe(|v| emitted.push(v));
+  ///
+  ///   source.next(1);
+  ///   notifier.next(());
+  ///   source.next(2);
+  /// }
+  /// assert_eq!(emitted, vec![2]);
+  /// ```
+  #[inline]
+  fn skip_until<T>(self, notifier: T) -> SkipUntilOp<Self, T>
+  where
+    Self: Sized,
+  {
+    SkipUntilOp {
+      source: self,
+      notifier,
+    }
+  }
+
+  /// Discards values while result of a callback is true.
+  ///
+  /// `skip_while` returns an Observable that discards the values emitted by
+  /// the source Observable as long as the `callback` returns true. Starting
+  /// with the first value the `callback` returns false for, all values are
+  /// emitted and the `callback` is not called anymore.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .skip_while(|v| v < &5)
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print logs:
+  /// // 5
+  /// // 6
+  /// // 7
+  /// // 8
+  /// // 9
+  /// ```
+  #[inline]
+  fn skip_while<F>(self, callback: F) -> SkipWhileOp<Self, F>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Item) -> bool,
+  {
+    SkipWhileOp {
+      source: self,
+      callback,
+    }
+  }
+
+  /// Emits the previous and the current value of the source Observable as a
+  /// `(prev, current)` pair, starting with its second value.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec![1, 3, 6])
+  ///   .pairwise()
+  ///   .subscribe(|(prev, current)| println!("{}", current - prev));
+  ///
+  /// // print logs:
+  /// // 2
+  /// // 3
+  /// ```
+  #[inline]
+  fn pairwise(self) -> PairwiseOp<Self>
+  where
+    Self: Sized,
+  {
+    PairwiseOp { source: self }
+  }
+
+  /// Emits a `Vec` of the last `size` values of the source Observable,
+  /// oldest first, every time it emits, starting with its `size`th value.
+  ///
+  /// `sliding(2)` is the `Vec` counterpart of
+  /// [`pairwise`](Observable::pairwise).
+  ///
+  /// # Panics
+  ///
+  /// Panics if `size` is 0.
   ///
   /// # Example
-  /// ```
-  /// use rxrust::prelude::*;
-  /// use ops::box_it::LocalBoxOp;
   ///
-  /// let mut boxed: LocalBoxOp<'_, i32, ()> = observable::of(1)
-  ///   .map(|v| v).box_it();
+  /// ```
+  /// # use rxrust::prelude::*;
   ///
-  /// // BoxOp can box any observable type
-  /// boxed = observable::empty().box_it();
+  /// observable::from_iter(0..4)
+  ///   .sliding(3)
+  ///   .subscribe(|v| println!("{:?}", v));
   ///
-  /// boxed.subscribe(|_| {});
+  /// // print logs:
+  /// // [0, 1, 2]
+  /// // [1, 2, 3]
   /// ```
   #[inline]
-  fn box_it<O: IntoBox<Self>>(self) -> BoxOp<O>
+  fn sliding(self, size: usize) -> SlidingOp<Self>
   where
     Self: Sized,
-    BoxOp<O>: Observable<Item = Self::Item, Err = Self::Err>,
   {
-    O::box_it(self)
+    assert!(size != 0, "window size must be non-zero");
+    SlidingOp { source: self, size }
   }
 
-  /// Ignore the first `count` values emitted by the source Observable.
+  /// Emits the given `items` before the values of the source Observable.
   ///
-  /// `skip` returns an Observable that ignore the first `count` values
-  /// emitted by the source Observable. If the source emits fewer than `count`
-  /// values then 0 of its values are emitted. After that, it completes,
-  /// regardless if the source completes.
+  /// The `items` are emitted synchronously on subscription, before the
+  /// source is subscribed to, so even a hot source can't emit in between.
   ///
   /// # Example
-  /// Ignore the first 5 seconds of an infinite 1-second interval Observable
   ///
   /// ```
   /// # use rxrust::prelude::*;
   ///
-  /// observable::from_iter(0..10).skip(5).subscribe(|v| println!("{}", v));
-
+  /// observable::from_iter(2..4)
+  ///   .start_with(vec![0, 1])
+  ///   .subscribe(|v| println!("{}", v));
+  ///
   /// // print logs:
-  /// // 6
-  /// // 7
-  /// // 8
-  /// // 9
-  /// // 10
+  /// // 0
+  /// // 1
+  /// // 2
+  /// // 3
   /// ```
   #[inline]
-  fn skip(self, count: u32) -> SkipOp<Self>
+  fn start_with<I>(self, items: I) -> StartWithOp<Self, Self::Item>
   where
     Self: Sized,
+    I: IntoIterator<Item = Self::Item>,
   {
-    SkipOp {
+    StartWithOp {
       source: self,
-      count,
+      items: items.into_iter().collect(),
     }
   }
 
-  /// Ignore the last `count` values emitted by the source Observable.
+  /// Emits the given `items` after the source Observable completed, right
+  /// before completing.
   ///
-  /// `skip_last` returns an Observable that ignore the last `count` values
-  /// emitted by the source Observable. If the source emits fewer than `count`
-  /// values then 0 of its values are emitted.
-  /// It will not emit values until source Observable complete.
+  /// Nothing is appended if the source errors.
   ///
   /// # Example
-  /// Skip the last 5 seconds of an infinite 1-second interval Observable
   ///
   /// ```
   /// # use rxrust::prelude::*;
   ///
-  /// observable::from_iter(0..10)
-  ///   .skip_last(5)
+  /// observable::from_iter(0..2)
+  ///   .end_with(vec![8, 9])
   ///   .subscribe(|v| println!("{}", v));
   ///
   /// // print logs:
   /// // 0
   /// // 1
-  /// // 2
-  /// // 3
-  /// // 4
+  /// // 8
+  /// // 9
   /// ```
   #[inline]
-  fn skip_last(self, count: usize) -> SkipLastOp<Self>
+  fn end_with<I>(self, items: I) -> EndWithOp<Self, Self::Item>
   where
     Self: Sized,
+    I: IntoIterator<Item = Self::Item>,
   {
-    SkipLastOp {
+    EndWithOp {
       source: self,
-      count,
+      items: items.into_iter().collect(),
     }
   }
 
@@ -546,7 +1398,7 @@ pub trait Observable {
   ) -> ScanOp<Self, BinaryOp, OutputItem>
   where
     Self: Sized,
-    BinaryOp: Fn(OutputItem, Self::Item) -> OutputItem,
+    BinaryOp: FnMut(OutputItem, Self::Item) -> OutputItem,
     OutputItem: Clone,
   {
     ScanOp {
@@ -570,7 +1422,7 @@ pub trait Observable {
   ) -> ScanOp<Self, BinaryOp, OutputItem>
   where
     Self: Sized,
-    BinaryOp: Fn(OutputItem, Self::Item) -> OutputItem,
+    BinaryOp: FnMut(OutputItem, Self::Item) -> OutputItem,
     OutputItem: Default + Clone,
   {
     self.scan_initial(OutputItem::default(), binary_op)
@@ -605,75 +1457,335 @@ pub trait Observable {
   ) -> ReduceOp<Self, BinaryOp, OutputItem>
   where
     Self: Sized,
-    BinaryOp: Fn(OutputItem, Self::Item) -> OutputItem,
-    OutputItem: Clone,
+    BinaryOp: FnMut(OutputItem, Self::Item) -> OutputItem,
+    OutputItem: Clone,
+  {
+    // realised as a composition of `scan`, and `last`
+    self
+      .scan_initial(initial.clone(), binary_op)
+      .last_or(initial)
+  }
+
+  /// Works like [`reduce_initial`](Observable::reduce_initial) but starts with
+  /// a value defined by a [`Default`] trait for the first argument `f`
+  /// operator operates on.
+  ///
+  /// # Arguments
+  ///
+  /// * `binary_op` - A closure acting as a binary operator.
+  #[inline]
+  fn reduce<OutputItem, BinaryOp>(
+    self,
+    binary_op: BinaryOp,
+  ) -> LastOrOp<ScanOp<Self, BinaryOp, OutputItem>, OutputItem>
+  where
+    Self: Sized,
+    BinaryOp: FnMut(OutputItem, Self::Item) -> OutputItem,
+    OutputItem: Default + Clone,
+  {
+    self.reduce_initial(OutputItem::default(), binary_op)
+  }
+
+  /// Emits the item from the source observable that had the maximum value.
+  ///
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec![3., 4., 7., 5., 6.])
+  ///   .max()
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 7
+  /// ```
+  fn max(self) -> MinMaxOp<Self, Self::Item>
+  where
+    Self: Sized,
+    Self::Item: Clone + Send + PartialOrd<Self::Item>,
+  {
+    fn get_greater<Item>(i: Option<Item>, v: Item) -> Option<Item>
+    where
+      Item: Clone + PartialOrd<Item>,
+    {
+      i.map(|vv| if vv < v { v.clone() } else { vv }).or(Some(v))
+    }
+    let get_greater_func =
+      get_greater as fn(Option<Self::Item>, Self::Item) -> Option<Self::Item>;
+
+    self
+      .scan_initial(None, get_greater_func)
+      .last()
+      // we can safely unwrap, because we will ever get this item
+      // once a max value exists and is there.
+      .map(|v| v.unwrap())
+  }
+
+  /// Emits the item from the source observable that had the minimum value.
+  ///
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec![3., 4., 7., 5., 6.])
+  ///   .min()
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 3
+  /// ```
+  fn min(self) -> MinMaxOp<Self, Self::Item>
+  where
+    Self: Sized,
+    Self::Item: Clone + Send + PartialOrd<Self::Item>,
+  {
+    fn get_lesser<Item>(i: Option<Item>, v: Item) -> Option<Item>
+    where
+      Item: Clone + PartialOrd<Item>,
+    {
+      i.map(|vv| if vv > v { v.clone() } else { vv }).or(Some(v))
+    }
+
+    let get_lesser_func =
+      get_lesser as fn(Option<Self::Item>, Self::Item) -> Option<Self::Item>;
+
+    self
+      .scan_initial(None, get_lesser_func)
+      .last()
+      // we can safely unwrap, because we will ever get this item
+      // once a max value exists and is there.
+      .map(|v| v.unwrap())
+  }
+
+  /// Emits the item from the source observable that is the minimum with
+  /// respect to the `compare` function. If several items are equally minimum,
+  /// the first one is emitted.
+  ///
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec!["bb", "a", "ccc"])
+  ///   .min_by(|a, b| a.len().cmp(&b.len()))
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // a
+  /// ```
+  #[inline]
+  fn min_by<F>(self, compare: F) -> MinMaxByOp<Self, Self::Item, F>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+  {
+    ReduceByOp {
+      source: self,
+      accumulator: MinMaxBy::new(compare, false),
+    }
+  }
+
+  /// Emits the item from the source observable that is the maximum with
+  /// respect to the `compare` function. If several items are equally maximum,
+  /// the last one is emitted.
+  ///
+  /// Emits error when source observable emits it.
+  #[inline]
+  fn max_by<F>(self, compare: F) -> MinMaxByOp<Self, Self::Item, F>
+  where
+    Self: Sized,
+    F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+  {
+    ReduceByOp {
+      source: self,
+      accumulator: MinMaxBy::new(compare, true),
+    }
+  }
+
+  /// Emits the item from the source observable that gives the minimum value
+  /// from the `key` function. If several items are equally minimum, the first
+  /// one is emitted.
+  ///
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec![(3, 'a'), (1, 'b'), (2, 'c')])
+  ///   .min_by_key(|p| p.0)
+  ///   .subscribe(|v| println!("{:?}", v));
+  ///
+  /// // print log:
+  /// // (1, 'b')
+  /// ```
+  #[inline]
+  fn min_by_key<K, F>(self, key: F) -> MinMaxByOp<Self, Self::Item, ByKey<F>>
+  where
+    Self: Sized,
+    K: Ord,
+    F: FnMut(&Self::Item) -> K,
+  {
+    ReduceByOp {
+      source: self,
+      accumulator: MinMaxBy::new(ByKey(key), false),
+    }
+  }
+
+  /// Emits the item from the source observable that gives the maximum value
+  /// from the `key` function. If several items are equally maximum, the last
+  /// one is emitted.
+  ///
+  /// Emits error when source observable emits it.
+  #[inline]
+  fn max_by_key<K, F>(self, key: F) -> MinMaxByOp<Self, Self::Item, ByKey<F>>
+  where
+    Self: Sized,
+    K: Ord,
+    F: FnMut(&Self::Item) -> K,
+  {
+    ReduceByOp {
+      source: self,
+      accumulator: MinMaxBy::new(ByKey(key), true),
+    }
+  }
+
+  /// Emits how many items of the source observable share each key returned by
+  /// the `key` function, once the source completes.
+  ///
+  /// Emits an empty map when source completed as an empty sequence.
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..10)
+  ///   .count_by(|v| v % 2 == 0)
+  ///   .subscribe(|v| println!("{:?}", v[&true]));
+  ///
+  /// // print log:
+  /// // 5
+  /// ```
+  #[inline]
+  fn count_by<K, F>(self, key: F) -> CountByOp<Self, K, F>
+  where
+    Self: Sized,
+    K: Hash + Eq,
+    F: FnMut(&Self::Item) -> K,
+  {
+    ReduceByOp {
+      source: self,
+      accumulator: CountBy::new(key),
+    }
+  }
+
+  /// Emits the items of the source observable grouped by the key returned by
+  /// the `key` function, once the source completes. The items of each group
+  /// keep the order in which they were emitted.
+  ///
+  /// Emits an empty map when source completed as an empty sequence.
+  /// Emits error when source observable emits it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(vec!["apple", "banana", "avocado"])
+  ///   .to_map_by(|s| s.len() > 5)
+  ///   .subscribe(|v| println!("{:?}", v[&true]));
+  ///
+  /// // print log:
+  /// // ["banana", "avocado"]
+  /// ```
+  #[inline]
+  fn to_map_by<K, F>(self, key: F) -> ToMapByOp<Self, Self::Item, K, F>
+  where
+    Self: Sized,
+    K: Hash + Eq,
+    F: FnMut(&Self::Item) -> K,
   {
-    // realised as a composition of `scan`, and `last`
-    self
-      .scan_initial(initial.clone(), binary_op)
-      .last_or(initial)
+    ReduceByOp {
+      source: self,
+      accumulator: ToMapBy::new(key),
+    }
   }
 
-  /// Works like [`reduce_initial`](Observable::reduce_initial) but starts with
-  /// a value defined by a [`Default`] trait for the first argument `f`
-  /// operator operates on.
+  /// Emits all items of the source observable in a [`Vec`], once the source
+  /// completes.
   ///
-  /// # Arguments
+  /// Emits an empty vector when source completed as an empty sequence.
+  /// Emits error when source observable emits it.
   ///
-  /// * `binary_op` - A closure acting as a binary operator.
+  /// # Examples
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .to_vec()
+  ///   .subscribe(|v| println!("{:?}", v));
+  ///
+  /// // print log:
+  /// // [0, 1, 2]
+  /// ```
   #[inline]
-  fn reduce<OutputItem, BinaryOp>(
-    self,
-    binary_op: BinaryOp,
-  ) -> LastOrOp<ScanOp<Self, BinaryOp, OutputItem>, OutputItem>
+  fn to_vec(self) -> CollectOp<Self, Vec<Self::Item>>
   where
     Self: Sized,
-    BinaryOp: Fn(OutputItem, Self::Item) -> OutputItem,
-    OutputItem: Default + Clone,
   {
-    self.reduce_initial(OutputItem::default(), binary_op)
+    self.collect()
   }
 
-  /// Emits the item from the source observable that had the maximum value.
+  /// Emits all items of the source observable collected into `C`, once the
+  /// source completes. Every collection of the standard library that can be
+  /// built with [`Iterator::collect`] can be used.
   ///
+  /// Emits an empty collection when source completed as an empty sequence.
   /// Emits error when source observable emits it.
   ///
   /// # Examples
   ///
   /// ```
   /// use rxrust::prelude::*;
+  /// use std::collections::BTreeSet;
   ///
-  /// observable::from_iter(vec![3., 4., 7., 5., 6.])
-  ///   .max()
-  ///   .subscribe(|v| println!("{}", v));
+  /// observable::from_iter(vec![3, 1, 3, 2])
+  ///   .collect::<BTreeSet<_>>()
+  ///   .subscribe(|v| println!("{:?}", v));
   ///
   /// // print log:
-  /// // 7
+  /// // {1, 2, 3}
   /// ```
-  fn max(self) -> MinMaxOp<Self, Self::Item>
+  #[inline]
+  fn collect<C>(self) -> CollectOp<Self, C>
   where
     Self: Sized,
-    Self::Item: Clone + Send + PartialOrd<Self::Item>,
+    C: Default + Extend<Self::Item>,
   {
-    fn get_greater<Item>(i: Option<Item>, v: Item) -> Option<Item>
-    where
-      Item: Clone + PartialOrd<Item>,
-    {
-      i.map(|vv| if vv < v { v.clone() } else { vv }).or(Some(v))
+    CollectOp {
+      source: self,
+      collection: C::default(),
     }
-    let get_greater_func =
-      get_greater as fn(Option<Self::Item>, Self::Item) -> Option<Self::Item>;
-
-    self
-      .scan_initial(None, get_greater_func)
-      .last()
-      // we can safely unwrap, because we will ever get this item
-      // once a max value exists and is there.
-      .map(|v| v.unwrap())
   }
 
-  /// Emits the item from the source observable that had the minimum value.
+  /// Emits a [`HashMap`](std::collections::HashMap) with an entry for every
+  /// item of the source observable, made of the results of the `key` and
+  /// `value` functions, once the source completes. Items with the same key
+  /// replace earlier ones.
   ///
+  /// Emits an empty map when source completed as an empty sequence.
   /// Emits error when source observable emits it.
   ///
   /// # Examples
@@ -681,34 +1793,29 @@ pub trait Observable {
   /// ```
   /// use rxrust::prelude::*;
   ///
-  /// observable::from_iter(vec![3., 4., 7., 5., 6.])
-  ///   .min()
-  ///   .subscribe(|v| println!("{}", v));
+  /// observable::from_iter(vec!["a", "bb"])
+  ///   .to_hash_map(|s| s.len(), |s| s.to_uppercase())
+  ///   .subscribe(|v| println!("{:?}", v[&2]));
   ///
   /// // print log:
-  /// // 3
+  /// // "BB"
   /// ```
-  fn min(self) -> MinMaxOp<Self, Self::Item>
+  #[inline]
+  fn to_hash_map<K, V, FK, FV>(
+    self,
+    key: FK,
+    value: FV,
+  ) -> ToHashMapOp<Self, K, V, FK, FV>
   where
     Self: Sized,
-    Self::Item: Clone + Send + PartialOrd<Self::Item>,
+    K: Hash + Eq,
+    FK: FnMut(&Self::Item) -> K,
+    FV: FnMut(Self::Item) -> V,
   {
-    fn get_lesser<Item>(i: Option<Item>, v: Item) -> Option<Item>
-    where
-      Item: Clone + PartialOrd<Item>,
-    {
-      i.map(|vv| if vv > v { v.clone() } else { vv }).or(Some(v))
+    ReduceByOp {
+      source: self,
+      accumulator: ToHashMap::new(key, value),
     }
-
-    let get_lesser_func =
-      get_lesser as fn(Option<Self::Item>, Self::Item) -> Option<Self::Item>;
-
-    self
-      .scan_initial(None, get_lesser_func)
-      .last()
-      // we can safely unwrap, because we will ever get this item
-      // once a max value exists and is there.
-      .map(|v| v.unwrap())
   }
 
   /// Calculates the sum of numbers emitted by an source observable and emits
@@ -829,6 +1936,118 @@ pub trait Observable {
     self.scan_initial(start, acc).last().map(avg)
   }
 
+  /// Emits the mean of the values in `window` every time the source emits.
+  /// Works for every [`Numeric`](ops::statistics::Numeric) item type,
+  /// including integers.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::{prelude::*, ops::statistics::Window};
+  ///
+  /// observable::from_iter(vec![1, 2, 3, 10])
+  ///   .moving_average(Window::Count(2))
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 1
+  /// // 1.5
+  /// // 2.5
+  /// // 6.5
+  /// ```
+  #[inline]
+  fn moving_average(
+    self,
+    window: Window,
+  ) -> StatisticOp<Self, Mean, SystemClock>
+  where
+    Self: Sized,
+    Self::Item: Numeric,
+  {
+    self.statistic_with(Mean::default(), window, SystemClock)
+  }
+
+  /// Emits the population variance of the values in `window` every time the
+  /// source emits.
+  #[inline]
+  fn moving_variance(
+    self,
+    window: Window,
+  ) -> StatisticOp<Self, Variance, SystemClock>
+  where
+    Self: Sized,
+    Self::Item: Numeric,
+  {
+    self.statistic_with(Variance::default(), window, SystemClock)
+  }
+
+  /// Emits the population standard deviation of the values in `window` every
+  /// time the source emits.
+  #[inline]
+  fn moving_std_dev(
+    self,
+    window: Window,
+  ) -> StatisticOp<Self, StdDev, SystemClock>
+  where
+    Self: Sized,
+    Self::Item: Numeric,
+  {
+    self.statistic_with(StdDev::default(), window, SystemClock)
+  }
+
+  /// Emits the `percentile` (between 0 and 100) of the values in `window`
+  /// every time the source emits. See
+  /// [`Percentile`](ops::statistics::Percentile) for how it's computed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::{prelude::*, ops::statistics::Window};
+  ///
+  /// observable::from_iter(vec![5, 1, 4, 2])
+  ///   .moving_percentile(Window::All, 50.)
+  ///   .last()
+  ///   .subscribe(|v| println!("{}", v));
+  ///
+  /// // print log:
+  /// // 3
+  /// ```
+  #[inline]
+  fn moving_percentile(
+    self,
+    window: Window,
+    percentile: f64,
+  ) -> StatisticOp<Self, Percentile, SystemClock>
+  where
+    Self: Sized,
+    Self::Item: Numeric,
+  {
+    self.statistic_with(Percentile::new(percentile), window, SystemClock)
+  }
+
+  /// Emits the given [`Statistic`](ops::statistics::Statistic) of the values
+  /// in `window` every time the source emits. Time windows are measured with
+  /// `clock`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `window` is a count window of size zero.
+  #[inline]
+  fn statistic_with<T, C>(
+    self,
+    statistic: T,
+    window: Window,
+    clock: C,
+  ) -> StatisticOp<Self, T, C>
+  where
+    Self: Sized,
+    Self::Item: Numeric,
+    T: Statistic,
+    C: Clock,
+  {
+    StatisticOp::new(self, statistic, window, clock)
+  }
+
   /// Returns a ConnectableObservable. A ConnectableObservable Observable
   /// resembles an ordinary Observable, except that it does not begin emitting
   /// items when it is subscribed to, but only when the Connect operator is
@@ -863,6 +2082,63 @@ pub trait Observable {
     self.publish::<Subject>().ref_count::<Inner>()
   }
 
+  /// Splits the source Observable into two: the first emits the values that
+  /// satisfy the `predicate`, the second the ones that don't.
+  ///
+  /// Both halves are built on `publish().ref_count()`, so the source is
+  /// subscribed to only once, as soon as the first half is subscribed to,
+  /// and unsubscribed from when both halves are. Like with
+  /// [`share`](Observable::share), a cold source emitting synchronously on
+  /// subscription finishes before the second half is subscribed to, so it's
+  /// meant for hot sources.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use rxrust::prelude::*;
+  /// let mut records = Subject::new();
+  /// let (valid, invalid) = records.clone().partition(|v: &i32| *v >= 0);
+  /// valid.subscribe(|v| println!("valid {}", v));
+  /// invalid.subscribe(|v| println!("invalid {}", v));
+  ///
+  /// records.next(1);
+  /// records.next(-1);
+  ///
+  /// // print log:
+  /// // valid 1
+  /// // invalid -1
+  /// ```
+  #[inline]
+  #[allow(clippy::type_complexity)]
+  fn partition<F, Subject, Inner>(
+    self,
+    predicate: F,
+  ) -> (
+    Partition<Self, Subject, Inner, F>,
+    Partition<Self, Subject, Inner, F>,
+  )
+  where
+    Inner: RefCountCreator<Connectable = ConnectableObservable<Self, Subject>>
+      + Clone,
+    Subject: Default,
+    Self: Sized + Clone,
+    F: Fn(&Self::Item) -> bool + Clone,
+  {
+    let shared = self.share::<Subject, Inner>();
+    (
+      PartitionOp {
+        source: shared.clone(),
+        predicate: predicate.clone(),
+        matching: true,
+      },
+      PartitionOp {
+        source: shared,
+        predicate,
+        matching: false,
+      },
+    )
+  }
+
   /// Delays the emission of items from the source Observable by a given timeout
   /// or until a given `Instant`.
   #[inline]
@@ -887,6 +2163,116 @@ pub trait Observable {
     }
   }
 
+  /// Delays the emission of items from the source Observable like
+  /// [`delay`](Observable::delay), but waits on the single-threaded executor
+  /// behind `spawner`, such as a `futures::executor::LocalPool`, so neither
+  /// the source nor the observer have to be `Send`.
+  ///
+  /// `spawner` is a `LocalSpawn`, which a
+  /// [`LocalScheduler`](crate::scheduler::LocalScheduler) is as well, but not
+  /// any [`LocalSchedulerLike`](crate::scheduler::LocalSchedulerLike): the
+  /// delay always passes on the wall clock.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use futures::executor::LocalPool;
+  /// use rxrust::prelude::*;
+  /// use std::{cell::Cell, rc::Rc, time::Duration};
+  ///
+  /// let mut pool = LocalPool::new();
+  /// let value = Rc::new(Cell::new(0));
+  /// let c_value = value.clone();
+  /// observable::of(1)
+  ///   .local_delay(Duration::from_millis(1), pool.spawner())
+  ///   .subscribe(move |v| c_value.set(v));
+  /// assert_eq!(value.get(), 0);
+  /// pool.run();
+  /// assert_eq!(value.get(), 1);
+  /// ```
+  #[inline]
+  fn local_delay<Sp>(self, dur: Duration, spawner: Sp) -> LocalDelayOp<Self, Sp>
+  where
+    Self: Sized,
+    Sp: LocalSpawn,
+  {
+    LocalDelayOp {
+      source: self,
+      delay: dur,
+      spawner,
+    }
+  }
+
+  /// Attaches the time of emission to every value emitted by the source
+  /// Observable, as a [`Timestamped`](crate::ops::timestamp::Timestamped).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .timestamp()
+  ///   .subscribe(|v| println!("{} at {:?}", v.value, v.at));
+  /// ```
+  #[inline]
+  fn timestamp(self) -> TimestampOp<Self, SystemClock>
+  where
+    Self: Sized,
+  {
+    self.timestamp_with(SystemClock)
+  }
+
+  /// Works like [`timestamp`](Observable::timestamp) but reads the time from
+  /// the given `clock`.
+  #[inline]
+  fn timestamp_with<C>(self, clock: C) -> TimestampOp<Self, C>
+  where
+    Self: Sized,
+    C: Clock,
+  {
+    TimestampOp {
+      source: self,
+      clock,
+    }
+  }
+
+  /// Emits every value of the source Observable together with the time
+  /// elapsed since the previous value, as a
+  /// [`TimeInterval`](crate::ops::time_interval::TimeInterval). The first
+  /// value measures the time elapsed since subscription.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  ///
+  /// observable::from_iter(0..3)
+  ///   .time_interval()
+  ///   .subscribe(|v| println!("{} after {:?}", v.value, v.elapsed));
+  /// ```
+  #[inline]
+  fn time_interval(self) -> TimeIntervalOp<Self, SystemClock>
+  where
+    Self: Sized,
+  {
+    self.time_interval_with(SystemClock)
+  }
+
+  /// Works like [`time_interval`](Observable::time_interval) but reads the
+  /// time from the given `clock`.
+  #[inline]
+  fn time_interval_with<C>(self, clock: C) -> TimeIntervalOp<Self, C>
+  where
+    Self: Sized,
+    C: Clock,
+  {
+    TimeIntervalOp {
+      source: self,
+      clock,
+    }
+  }
+
   /// Specify the Scheduler on which an Observable will operate
   ///
   /// With `SubscribeON` you can decide what type of scheduler a specific
@@ -988,6 +2374,35 @@ pub trait Observable {
     }
   }
 
+  /// Throttles the source Observable like
+  /// [`throttle_time`](Observable::throttle_time), but waits on the
+  /// single-threaded executor behind `spawner`, such as a
+  /// `futures::executor::LocalPool`, so neither the source nor the observer
+  /// have to be `Send`.
+  ///
+  /// Like [`local_delay`](Observable::local_delay), it takes a `LocalSpawn`
+  /// rather than a
+  /// [`LocalSchedulerLike`](crate::scheduler::LocalSchedulerLike), so the
+  /// throttle windows always last on the wall clock.
+  #[inline]
+  fn local_throttle_time<Sp>(
+    self,
+    duration: Duration,
+    edge: ThrottleEdge,
+    spawner: Sp,
+  ) -> LocalThrottleTimeOp<Self, Sp>
+  where
+    Self: Sized,
+    Sp: LocalSpawn,
+  {
+    LocalThrottleTimeOp {
+      source: self,
+      duration,
+      edge,
+      spawner,
+    }
+  }
+
   /// 'Zips up' two observable into a single observable of pairs.
   ///
   /// zip() returns a new observable that will emit over two other
@@ -1031,12 +2446,72 @@ pub trait Observable {
   }
 }
 
+fn noop_tap_next<Item>(_: &Item) {}
+fn noop_tap_error<Err>(_: &Err) {}
+fn noop_tap_complete() {}
+
 pub trait LocalObservable<'a>: Observable {
   type Unsub: SubscriptionLike + 'static;
   fn actual_subscribe<O: Observer<Self::Item, Self::Err> + 'a>(
     self,
     subscriber: Subscriber<O, LocalSubscription>,
   ) -> Self::Unsub;
+
+  /// Converts to a [`Stream`](futures::stream::Stream) which needn't be
+  /// `Send`, to be polled on the thread it was created on. See
+  /// [`ObservableStream`](into_stream::ObservableStream) for how it behaves.
+  #[inline]
+  fn into_local_stream(self) -> LocalObservableStream<Self>
+  where
+    Self: Sized,
+  {
+    ObservableStream::new(self, Rc::new(RefCell::new(StreamState::default())))
+  }
+
+  /// Converts to a [`Future`](futures::future::Future) resolving to the last
+  /// value of this Observable, once it completes, to be polled on the thread
+  /// it was created on. It's the same as
+  /// [`last_local_value`](LocalObservable::last_local_value).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use futures::executor::block_on;
+  /// use rxrust::prelude::*;
+  ///
+  /// let sum = block_on(observable::from_iter(0..4).sum().to_local_future());
+  /// assert_eq!(sum, Ok(6));
+  /// ```
+  #[inline]
+  fn to_local_future(self) -> LocalObservableFuture<Self>
+  where
+    Self: Sized,
+  {
+    self.last_local_value()
+  }
+
+  /// Converts to a [`Future`](futures::future::Future) resolving to the first
+  /// value of this Observable, to be polled on the thread it was created on.
+  /// See [`ObservableFuture`](to_future::ObservableFuture) for how it behaves.
+  #[inline]
+  fn first_local_value(self) -> LocalObservableFuture<TakeOp<Self>>
+  where
+    Self: Sized,
+  {
+    ObservableFuture::new(self.first().into_local_stream(), true)
+  }
+
+  /// Converts to a [`Future`](futures::future::Future) resolving to the last
+  /// value of this Observable, once it completes, to be polled on the thread
+  /// it was created on. See [`ObservableFuture`](to_future::ObservableFuture)
+  /// for how it behaves.
+  #[inline]
+  fn last_local_value(self) -> LocalObservableFuture<Self>
+  where
+    Self: Sized,
+  {
+    ObservableFuture::new(self.into_local_stream().bounded(1), false)
+  }
 }
 
 #[doc(hidden)]
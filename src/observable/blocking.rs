@@ -0,0 +1,290 @@
+use crate::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
+use std::time::{Duration, Instant};
+
+/// Why a blocking call returned without a value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockingError<Err> {
+  /// The Observable completed without emitting any value.
+  Empty,
+  /// The timeout elapsed before the Observable emitted the value.
+  Timeout,
+  /// The Observable emitted an error.
+  Err(Err),
+}
+
+impl<Err: Display> Display for BlockingError<Err> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      BlockingError::Empty => write!(f, "observable completed without a value"),
+      BlockingError::Timeout => write!(f, "observable timed out"),
+      BlockingError::Err(err) => err.fmt(f),
+    }
+  }
+}
+
+impl<Err: std::error::Error> std::error::Error for BlockingError<Err> {}
+
+/// An [`Iterator`] over the notifications of an Observable, which blocks the
+/// current thread until the next one arrives.
+///
+/// The Observable is only subscribed to when the first item is requested, and
+/// is unsubscribed from when the iterator is dropped. Every value is yielded
+/// as an `Ok` item. An error is yielded as an `Err` item, after which the
+/// iteration ends, just like it ends when the Observable completes. Values
+/// arriving faster than they are iterated are buffered.
+///
+/// This struct is created by the
+/// [`blocking_iter`](SharedObservable::blocking_iter) method.
+pub struct BlockingIter<S, Item, Err> {
+  source: Option<S>,
+  sender: Option<Sender<Result<Item, Err>>>,
+  receiver: Receiver<Result<Item, Err>>,
+  subscription: SharedSubscription,
+  // The iterator stays on the current thread, so the subscription of the
+  // source needn't be `Send`.
+  unsub: Option<Box<dyn SubscriptionLike>>,
+}
+
+impl<S, Item, Err> BlockingIter<S, Item, Err> {
+  pub(crate) fn new(source: S) -> Self {
+    let (sender, receiver) = channel();
+    BlockingIter {
+      source: Some(source),
+      sender: Some(sender),
+      receiver,
+      subscription: SharedSubscription::default(),
+      unsub: None,
+    }
+  }
+}
+
+impl<S, Item, Err> BlockingIter<S, Item, Err>
+where
+  S: SharedObservable<Item = Item, Err = Err>,
+  Item: Send + 'static,
+  Err: Send + 'static,
+{
+  /// Waits for the next item until `deadline`, if there is any. Returns
+  /// `Ok(None)` once the iteration has ended.
+  fn recv_until(
+    &mut self,
+    deadline: Option<Instant>,
+  ) -> Result<Option<Result<Item, Err>>, RecvTimeoutError> {
+    if let Some(source) = self.source.take() {
+      let unsub = source.actual_subscribe(Subscriber {
+        observer: BlockingObserver(Mutex::new(self.sender.take())),
+        subscription: self.subscription.clone(),
+      });
+      self.unsub = Some(Box::new(unsub));
+    }
+    let received = match deadline {
+      Some(deadline) => self
+        .receiver
+        .recv_timeout(deadline.saturating_duration_since(Instant::now())),
+      None => self
+        .receiver
+        .recv()
+        .map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+      Ok(item) => Ok(Some(item)),
+      Err(RecvTimeoutError::Disconnected) => Ok(None),
+      Err(RecvTimeoutError::Timeout) => Err(RecvTimeoutError::Timeout),
+    }
+  }
+
+  pub(crate) fn first(
+    mut self,
+    timeout: Option<Duration>,
+  ) -> Result<Item, BlockingError<Err>> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    match self.recv_until(deadline) {
+      Ok(Some(Ok(v))) => Ok(v),
+      Ok(Some(Err(err))) => Err(BlockingError::Err(err)),
+      Ok(None) => Err(BlockingError::Empty),
+      Err(_) => Err(BlockingError::Timeout),
+    }
+  }
+
+  pub(crate) fn last(
+    mut self,
+    timeout: Option<Duration>,
+  ) -> Result<Item, BlockingError<Err>> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let mut last = None;
+    loop {
+      match self.recv_until(deadline) {
+        Ok(Some(Ok(v))) => last = Some(v),
+        Ok(Some(Err(err))) => return Err(BlockingError::Err(err)),
+        Ok(None) => return last.ok_or(BlockingError::Empty),
+        Err(_) => return Err(BlockingError::Timeout),
+      }
+    }
+  }
+}
+
+impl<S, Item, Err> Iterator for BlockingIter<S, Item, Err>
+where
+  S: SharedObservable<Item = Item, Err = Err>,
+  Item: Send + 'static,
+  Err: Send + 'static,
+{
+  type Item = Result<Item, Err>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.recv_until(None).unwrap_or(None)
+  }
+}
+
+impl<S, Item, Err> Drop for BlockingIter<S, Item, Err> {
+  fn drop(&mut self) {
+    self.subscription.unsubscribe();
+    if let Some(unsub) = self.unsub.as_mut() {
+      unsub.unsubscribe();
+    }
+  }
+}
+
+// `Sender` isn't `Sync`, so it's guarded by a mutex. Dropping it on
+// termination ends the iteration.
+struct BlockingObserver<T>(Mutex<Option<Sender<T>>>);
+
+impl<Item, Err> Observer<Item, Err> for BlockingObserver<Result<Item, Err>> {
+  fn next(&mut self, value: Item) { self.send(Ok(value)) }
+
+  fn error(&mut self, err: Err) {
+    self.send(Err(err));
+    self.0.lock().unwrap().take();
+  }
+
+  fn complete(&mut self) { self.0.lock().unwrap().take(); }
+}
+
+impl<T> BlockingObserver<T> {
+  fn send(&self, value: T) {
+    if let Some(sender) = self.0.lock().unwrap().as_ref() {
+      // The iterator may have been dropped already, the value isn't needed
+      // anymore then.
+      let _ = sender.send(value);
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::BlockingError;
+  use crate::prelude::*;
+  use std::time::Duration;
+
+  #[test]
+  fn iter() {
+    let values: Vec<Result<_, ()>> =
+      observable::from_iter(0..3).blocking_iter().collect();
+    assert_eq!(values, vec![Ok(0), Ok(1), Ok(2)]);
+  }
+
+  #[test]
+  fn iter_other_thread() {
+    let values: Vec<_> = observable::from_iter(0..3)
+      .delay(Duration::from_millis(1))
+      .blocking_iter()
+      .map(Result::unwrap)
+      .collect();
+    assert_eq!(values, vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn iter_error() {
+    let mut subject = SharedSubject::new();
+    let mut iter = subject.clone().blocking_iter();
+    let mut c_subject = subject.clone();
+    std::thread::spawn(move || {
+      // Wait for the iterator to subscribe.
+      while c_subject.subscribed_size() == 0 {
+        std::thread::yield_now();
+      }
+      c_subject.next(1);
+      c_subject.error("oops");
+    });
+    assert_eq!(iter.next(), Some(Ok(1)));
+    assert_eq!(iter.next(), Some(Err("oops")));
+    assert_eq!(iter.next(), None);
+    subject.next(2);
+  }
+
+  #[test]
+  fn iter_drop_unsubscribes() {
+    let mut subject = SharedSubject::new();
+    let mut iter = subject.clone().blocking_iter();
+    let mut c_subject = subject.clone();
+    let emitter = std::thread::spawn(move || {
+      while c_subject.subscribed_size() == 0 {
+        std::thread::yield_now();
+      }
+      c_subject.next(1);
+    });
+    assert_eq!(iter.next(), Some(Ok::<_, ()>(1)));
+    emitter.join().unwrap();
+    drop(iter);
+    subject.next(2);
+    assert_eq!(subject.subscribed_size(), 0);
+  }
+
+  #[test]
+  fn subscribe() {
+    let mut values = vec![];
+    let res = observable::interval(Duration::from_millis(1))
+      .take(3)
+      .blocking_subscribe(|v| values.push(v));
+    assert_eq!(res, Ok(()));
+    assert_eq!(values, vec![0, 1, 2]);
+
+    let res = observable::from_future_result(futures::future::err("oops"))
+      .blocking_subscribe(|_: i32| {});
+    assert_eq!(res, Err("oops"));
+  }
+
+  #[test]
+  fn first_and_last() {
+    let o = observable::interval(Duration::from_millis(1)).take(3);
+    assert_eq!(o.clone().blocking_first(None), Ok(0));
+    assert_eq!(o.blocking_last(None), Ok(2));
+  }
+
+  #[test]
+  fn first_of_infinite_source() {
+    assert_eq!(
+      observable::from_iter(0..).blocking_first(None),
+      Ok::<_, BlockingError<()>>(0)
+    );
+  }
+
+  #[test]
+  fn empty_and_error() {
+    assert_eq!(
+      observable::empty::<i32>().blocking_last(None),
+      Err(BlockingError::<()>::Empty)
+    );
+    let (error, never) = (
+      observable::from_future_result(futures::future::err::<i32, _>("oops")),
+      observable::interval(Duration::from_secs(10)),
+    );
+    assert_eq!(error.blocking_first(None), Err(BlockingError::Err("oops")));
+    assert_eq!(
+      never.blocking_first(Some(Duration::from_millis(10))),
+      Err(BlockingError::Timeout)
+    );
+  }
+
+  #[test]
+  fn timeout_covers_all_values() {
+    let slow = observable::interval(Duration::from_millis(5));
+    assert_eq!(
+      slow.blocking_last(Some(Duration::from_millis(20))),
+      Err(BlockingError::Timeout)
+    );
+  }
+}
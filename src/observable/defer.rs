@@ -0,0 +1,136 @@
+use crate::prelude::*;
+
+/// Creates an Observable that calls `factory` to create a fresh Observable for
+/// every subscription, and mirrors it.
+///
+/// Nothing is created until the Observable is subscribed to, so the factory
+/// can read the state at that time, or start a side effect once per
+/// subscription. To subscribe more than once, the factory has to be `Clone`,
+/// and the Observable is cloned like any other.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::cell::Cell;
+///
+/// let state = Cell::new(1);
+/// let current = observable::defer(|| observable::of(state.get()));
+/// state.set(2);
+/// current.clone().subscribe(|v| println!("{}", v));
+/// state.set(3);
+/// current.subscribe(|v| println!("{}", v));
+///
+/// // print log:
+/// // 2
+/// // 3
+/// ```
+pub fn defer<F, S>(factory: F) -> DeferOp<F>
+where
+  F: FnOnce() -> S,
+  S: Observable,
+{
+  DeferOp(factory)
+}
+
+/// An Observable creating the Observable it mirrors on subscription.
+///
+/// This struct is created by the [`defer`] function.
+#[derive(Clone)]
+pub struct DeferOp<F>(F);
+
+impl<F, S> Observable for DeferOp<F>
+where
+  F: FnOnce() -> S,
+  S: Observable,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, F, S> LocalObservable<'a> for DeferOp<F>
+where
+  F: FnOnce() -> S,
+  S: LocalObservable<'a>,
+{
+  type Unsub = S::Unsub;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + 'a>(
+    self,
+    subscriber: Subscriber<O, LocalSubscription>,
+  ) -> Self::Unsub {
+    (self.0)().actual_subscribe(subscriber)
+  }
+}
+
+impl<F, S> SharedObservable for DeferOp<F>
+where
+  F: FnOnce() -> S + Send,
+  S: SharedObservable,
+{
+  type Unsub = S::Unsub;
+  fn actual_subscribe<
+    O: Observer<Self::Item, Self::Err> + Sync + Send + 'static,
+  >(
+    self,
+    subscriber: Subscriber<O, SharedSubscription>,
+  ) -> Self::Unsub {
+    (self.0)().actual_subscribe(subscriber)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::cell::Cell;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn fresh_observable_per_subscription() {
+    let created = Cell::new(0);
+    let mut values = vec![];
+    let o = observable::defer(|| {
+      created.set(created.get() + 1);
+      observable::of(created.get())
+    });
+    assert_eq!(created.get(), 0);
+    o.clone().subscribe(|v| values.push(v));
+    o.subscribe(|v| values.push(v));
+    assert_eq!(created.get(), 2);
+    assert_eq!(values, vec![1, 2]);
+  }
+
+  #[test]
+  fn shared() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let c_created = created.clone();
+    let sum = Arc::new(Mutex::new(0));
+    let c_sum = sum.clone();
+    let o = observable::defer(move || {
+      let n = c_created.fetch_add(1, Ordering::SeqCst) + 1;
+      observable::from_iter(0..n)
+    });
+    o.clone()
+      .to_shared()
+      .subscribe(move |v| *c_sum.lock().unwrap() += v);
+    let last = o.sum().blocking_last(None);
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+    assert_eq!(*sum.lock().unwrap(), 0);
+    assert_eq!(last, Ok(1));
+  }
+
+  #[test]
+  fn unsubscribe_source() {
+    let mut values = vec![];
+    {
+      let mut subject = LocalSubject::new();
+      let c_subject = subject.clone();
+      let mut subscription =
+        observable::defer(move || c_subject).subscribe(|v| values.push(v));
+      subject.next(1);
+      subscription.unsubscribe();
+      subject.next(2);
+    }
+    assert_eq!(values, vec![1]);
+  }
+}
@@ -0,0 +1,485 @@
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// Creates an Observable that waits for all `sources` to complete and then
+/// emits a `Vec` with the last value of each source, in the order of
+/// `sources`.
+///
+/// All sources are subscribed to at once. If any source errors, the error is
+/// forwarded and all other sources are unsubscribed from. If any source
+/// completes without emitting a value, the returned Observable completes
+/// without emitting as well. If `sources` is empty, it completes immediately.
+///
+/// Together with [`from_future`](crate::observable::from_future) this works
+/// like `futures::future::join_all`.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// observable::fork_join(vec![
+///   observable::from_iter(0..3),
+///   observable::from_iter(10..13),
+/// ])
+/// .subscribe(|v| assert_eq!(v, vec![2, 12]));
+/// ```
+///
+/// Use [`fork_join_with`](Observable::fork_join_with) to join two
+/// Observables of different types into a tuple.
+pub fn fork_join<I>(sources: I) -> ForkJoinOp<I::Item>
+where
+  I: IntoIterator,
+  I::Item: Observable,
+{
+  ForkJoinOp {
+    sources: sources.into_iter().collect(),
+  }
+}
+
+/// An Observable that emits the last values of all its sources once they all
+/// completed.
+///
+/// This struct is created by the [`fork_join`] function.
+#[derive(Clone)]
+pub struct ForkJoinOp<S> {
+  pub(crate) sources: Vec<S>,
+}
+
+/// An Observable that emits the last values of two Observables as a tuple
+/// once both completed.
+///
+/// This struct is created by the
+/// [`fork_join_with`](Observable::fork_join_with) method.
+#[derive(Clone)]
+pub struct ForkJoinWithOp<A, B> {
+  pub(crate) a: A,
+  pub(crate) b: B,
+}
+
+#[doc(hidden)]
+macro subscribe_source(
+  $subscription:ty, $source:expr, $index:expr, $tag:expr,
+  $observer:ident, $downstream:ident) {{
+  // Added upfront, so an error also stops a source that's still emitting
+  // synchronously.
+  let mut source_subscription = <$subscription>::default();
+  $downstream.add(source_subscription.clone());
+  source_subscription.add($source.actual_subscribe(Subscriber {
+    observer: IndexedObserver {
+      observer: $observer.clone(),
+      index: $index,
+      tag: $tag,
+    },
+    subscription: source_subscription.clone(),
+  }));
+}}
+
+#[doc(hidden)]
+macro observable_impl(
+  $subscription:ty, $sharer:path, $mutability_enabler:path,
+  $($marker:ident +)* $lf: lifetime)
+{
+  type Unsub = $subscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription;
+    let mut observer = subscriber.observer;
+    if self.sources.is_empty() {
+      observer.complete();
+      subscription.unsubscribe();
+      return subscription;
+    }
+    let observer = $sharer($mutability_enabler(ForkJoinObserver {
+      observer,
+      subscription: subscription.clone(),
+      values: self.sources.iter().map(|_| None).collect::<Vec<_>>(),
+      remaining: self.sources.len(),
+    }));
+    for (index, source) in self.sources.into_iter().enumerate() {
+      subscribe_source!(
+        $subscription,
+        source,
+        index,
+        |index, value| (index, value),
+        observer,
+        subscription
+      );
+    }
+    subscription
+  }
+}
+
+#[doc(hidden)]
+macro fork_join_with_observable_impl(
+  $subscription:ty, $sharer:path, $mutability_enabler:path,
+  $($marker:ident +)* $lf: lifetime)
+{
+  type Unsub = $subscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription;
+    let observer = $sharer($mutability_enabler(ForkJoinObserver {
+      observer: subscriber.observer,
+      subscription: subscription.clone(),
+      values: (None, None),
+      remaining: 2,
+    }));
+    subscribe_source!(
+      $subscription,
+      self.a,
+      0,
+      |_, value| Either::A(value),
+      observer,
+      subscription
+    );
+    subscribe_source!(
+      $subscription,
+      self.b,
+      1,
+      |_, value| Either::B(value),
+      observer,
+      subscription
+    );
+    subscription
+  }
+}
+
+impl<S> Observable for ForkJoinOp<S>
+where
+  S: Observable,
+{
+  type Item = Vec<S::Item>;
+  type Err = S::Err;
+}
+
+impl<'a, S> LocalObservable<'a> for ForkJoinOp<S>
+where
+  S: LocalObservable<'a>,
+  S::Item: 'a,
+{
+  observable_impl!(LocalSubscription, Rc::new, RefCell::new, 'a);
+}
+
+impl<S> SharedObservable for ForkJoinOp<S>
+where
+  S: SharedObservable,
+  S::Item: Send + Sync + 'static,
+  S::Unsub: Send + Sync,
+{
+  observable_impl!(
+    SharedSubscription,
+    Arc::new,
+    Mutex::new,
+    Send + Sync + 'static
+  );
+}
+
+impl<A, B> Observable for ForkJoinWithOp<A, B>
+where
+  A: Observable,
+  B: Observable<Err = A::Err>,
+{
+  type Item = (A::Item, B::Item);
+  type Err = A::Err;
+}
+
+impl<'a, A, B> LocalObservable<'a> for ForkJoinWithOp<A, B>
+where
+  A: LocalObservable<'a>,
+  A::Item: 'a,
+  B: LocalObservable<'a, Err = A::Err>,
+  B::Item: 'a,
+{
+  fork_join_with_observable_impl!(
+    LocalSubscription,
+    Rc::new,
+    RefCell::new,
+    'a
+  );
+}
+
+impl<A, B> SharedObservable for ForkJoinWithOp<A, B>
+where
+  A: SharedObservable,
+  A::Item: Send + Sync + 'static,
+  A::Unsub: Send + Sync,
+  B: SharedObservable<Err = A::Err>,
+  B::Item: Send + Sync + 'static,
+  B::Unsub: Send + Sync,
+{
+  fork_join_with_observable_impl!(
+    SharedSubscription,
+    Arc::new,
+    Mutex::new,
+    Send + Sync + 'static
+  );
+}
+
+/// The last values of the sources joined so far.
+trait JoinedValues {
+  /// A value tagged with the source emitting it.
+  type Value;
+  type Output;
+  fn set(&mut self, value: Self::Value);
+  fn is_set(&self, index: usize) -> bool;
+  fn take(&mut self) -> Self::Output;
+}
+
+impl<Item> JoinedValues for Vec<Option<Item>> {
+  type Value = (usize, Item);
+  type Output = Vec<Item>;
+
+  fn set(&mut self, (index, value): (usize, Item)) {
+    self[index] = Some(value);
+  }
+
+  fn is_set(&self, index: usize) -> bool { self[index].is_some() }
+
+  fn take(&mut self) -> Vec<Item> {
+    self.drain(..).map(Option::unwrap).collect()
+  }
+}
+
+enum Either<A, B> {
+  A(A),
+  B(B),
+}
+
+impl<A, B> JoinedValues for (Option<A>, Option<B>) {
+  type Value = Either<A, B>;
+  type Output = (A, B);
+
+  fn set(&mut self, value: Either<A, B>) {
+    match value {
+      Either::A(a) => self.0 = Some(a),
+      Either::B(b) => self.1 = Some(b),
+    }
+  }
+
+  fn is_set(&self, index: usize) -> bool {
+    if index == 0 {
+      self.0.is_some()
+    } else {
+      self.1.is_some()
+    }
+  }
+
+  fn take(&mut self) -> (A, B) {
+    (self.0.take().unwrap(), self.1.take().unwrap())
+  }
+}
+
+enum ForkJoinEvent<V> {
+  Next(V),
+  Complete(usize),
+}
+
+struct ForkJoinObserver<O, U, V> {
+  observer: O,
+  subscription: U,
+  values: V,
+  // Number of sources which didn't complete yet.
+  remaining: usize,
+}
+
+impl<O, U, V, Err> Observer<ForkJoinEvent<V::Value>, Err>
+  for ForkJoinObserver<O, U, V>
+where
+  O: Observer<V::Output, Err>,
+  U: SubscriptionLike,
+  V: JoinedValues,
+{
+  fn next(&mut self, event: ForkJoinEvent<V::Value>) {
+    match event {
+      ForkJoinEvent::Next(value) => self.values.set(value),
+      ForkJoinEvent::Complete(index) => {
+        if !self.values.is_set(index) {
+          self.observer.complete();
+          self.subscription.unsubscribe();
+          return;
+        }
+        self.remaining -= 1;
+        if self.remaining == 0 {
+          let values = self.values.take();
+          self.observer.next(values);
+          self.observer.complete();
+          self.subscription.unsubscribe();
+        }
+      }
+    }
+  }
+
+  fn error(&mut self, err: Err) {
+    self.observer.error(err);
+    self.subscription.unsubscribe();
+  }
+
+  fn complete(&mut self) {}
+}
+
+struct IndexedObserver<O, Item, V> {
+  observer: O,
+  index: usize,
+  tag: fn(usize, Item) -> V,
+}
+
+impl<O, Item, V, Err> Observer<Item, Err> for IndexedObserver<O, Item, V>
+where
+  O: Observer<ForkJoinEvent<V>, Err>,
+{
+  fn next(&mut self, value: Item) {
+    let value = (self.tag)(self.index, value);
+    self.observer.next(ForkJoinEvent::Next(value));
+  }
+
+  fn error(&mut self, err: Err) { self.observer.error(err); }
+
+  fn complete(&mut self) {
+    self.observer.next(ForkJoinEvent::Complete(self.index));
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use futures::future;
+  use std::cell::{Cell, RefCell};
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn smoke() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::fork_join(vec![
+      observable::from_iter(0..3),
+      observable::from_iter(5..7),
+      observable::from_iter(9..10),
+    ])
+    .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![vec![2, 6, 9]]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn wait_for_all() {
+    let emitted = RefCell::new(vec![]);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    observable::fork_join(vec![a.clone(), b.clone()])
+      .subscribe(|v| emitted.borrow_mut().push(v));
+    a.next(1);
+    b.next(2);
+    a.next(3);
+    a.complete();
+    assert!(emitted.borrow().is_empty());
+    b.complete();
+    assert_eq!(*emitted.borrow(), vec![vec![3, 2]]);
+  }
+
+  #[test]
+  fn error_cancels_others() {
+    let error = RefCell::new(None);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    observable::fork_join(vec![a.clone(), b.clone()])
+      .subscribe_err(|_| {}, |e| *error.borrow_mut() = Some(e));
+    a.next(1);
+    b.error("oops");
+    a.next(2);
+    assert_eq!(*error.borrow(), Some("oops"));
+    assert_eq!(a.subscribed_size(), 0);
+  }
+
+  #[test]
+  fn empty_source_completes_without_value() {
+    let mut next_count = 0;
+    let mut completed = false;
+    observable::fork_join(vec![
+      observable::from_iter(vec![]),
+      observable::from_iter(vec![1]),
+    ])
+    .subscribe_complete(|_| next_count += 1, || completed = true);
+    assert_eq!(next_count, 0);
+    assert!(completed);
+
+    completed = false;
+    observable::fork_join(Vec::<ObservableBase<EmptyEmitter<i32>>>::new())
+      .subscribe_complete(|_| next_count += 1, || completed = true);
+    assert_eq!(next_count, 0);
+    assert!(completed);
+  }
+
+  #[test]
+  fn futures() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    observable::fork_join(vec![
+      observable::from_future(future::ready(1)),
+      observable::from_future(future::ready(2)),
+    ])
+    .to_shared()
+    .subscribe(move |v| tx.send(v).unwrap());
+    assert_eq!(rx.recv().unwrap(), vec![1, 2]);
+  }
+
+  #[test]
+  fn fork_join_with() {
+    let emitted = Arc::new(Mutex::new(vec![]));
+    let c_emitted = emitted.clone();
+    observable::from_iter(0..3)
+      .fork_join_with(observable::of("a"))
+      .to_shared()
+      .subscribe(move |v| c_emitted.lock().unwrap().push(v));
+    assert_eq!(*emitted.lock().unwrap(), vec![(2, "a")]);
+  }
+
+  #[test]
+  fn fork_join_with_empty_side() {
+    let next_count = Cell::new(0);
+    let completed = Cell::new(false);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    a.clone().fork_join_with(b.clone()).subscribe_complete(
+      |_: (i32, i32)| next_count.set(next_count.get() + 1),
+      || completed.set(true),
+    );
+    a.complete();
+    assert!(completed.get());
+    b.next(1);
+    assert_eq!(b.subscribed_size(), 0);
+    b.complete();
+    assert_eq!(next_count.get(), 0);
+  }
+
+  #[test]
+  fn fork_join_with_error_side() {
+    let error = RefCell::new(None);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    a.clone()
+      .fork_join_with(b.clone())
+      .subscribe_err(|_: (i32, &str)| {}, |e| *error.borrow_mut() = Some(e));
+    a.next(1);
+    b.error("oops");
+    a.next(2);
+    assert_eq!(*error.borrow(), Some("oops"));
+    assert_eq!(a.subscribed_size(), 0);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::fork_join(vec![observable::of(1), observable::of(2)]);
+    o.clone().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+    let o = observable::of(1).fork_join_with(observable::of("a"));
+    o.clone().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+  }
+}
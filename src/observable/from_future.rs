@@ -17,17 +17,9 @@ lazy_static! {
 ///
 /// ```rust
 /// # use rxrust::prelude::*;
-/// # use std::sync::{Arc, Mutex};
-/// let res = Arc::new(Mutex::new(0));
-/// let c_res = res.clone();
 /// use futures::future;
-/// observable::from_future(future::ready(1))
-///   .to_shared()
-///   .subscribe(move |v| {
-///     *res.lock().unwrap() = v;
-///   });
-/// std::thread::sleep(std::time::Duration::new(1, 0));
-/// assert_eq!(*c_res.lock().unwrap(), 1);
+/// let res = observable::from_future(future::ready(1)).blocking_last(None);
+/// assert_eq!(res, Ok(1));
 /// ```
 /// If your `Future` poll an `Result` type value, and you want dispatch the
 /// error by rxrust, you can use [`from_future_result`]
@@ -108,25 +100,9 @@ where
 #[test]
 fn smoke() {
   use futures::future;
-  use std::sync::Arc;
-  let res = Arc::new(Mutex::new(0));
-  let c_res = res.clone();
-  {
-    from_future_result(future::ok(1))
-      .to_shared()
-      .subscribe(move |v| {
-        *res.lock().unwrap() = v;
-      });
-    std::thread::sleep(std::time::Duration::from_millis(10));
-    assert_eq!(*c_res.lock().unwrap(), 1);
-  }
-  // from_future
-  let res = c_res.clone();
-  from_future(future::ready(2))
-    .to_shared()
-    .subscribe(move |v| {
-      *res.lock().unwrap() = v;
-    });
-  std::thread::sleep(std::time::Duration::from_millis(10));
-  assert_eq!(*c_res.lock().unwrap(), 2);
+  assert_eq!(
+    from_future_result(future::ok::<_, ()>(1)).blocking_last(None),
+    Ok(1)
+  );
+  assert_eq!(from_future(future::ready(2)).blocking_last(None), Ok(2));
 }
@@ -0,0 +1,196 @@
+use crate::observable::interval::SpawnHandle;
+use crate::prelude::*;
+use futures::prelude::*;
+use futures::stream::{self, TryStream};
+use futures::task::{Spawn, SpawnExt};
+
+/// Creates an Observable that emits the items of `stream` and completes when
+/// it ends.
+///
+/// The stream is polled by a task spawned on `spawner` for every subscription,
+/// and the task is cancelled once unsubscribed from. Unlike
+/// [`from_future`](crate::observable::from_future), the stream needn't be
+/// `Clone` or `Sync`, but then the Observable can only be subscribed to
+/// once.
+///
+/// # Example
+///
+/// ```
+/// use futures::{executor::ThreadPool, stream};
+/// use rxrust::prelude::*;
+///
+/// let sum = observable::from_stream(stream::iter(1..4), ThreadPool::new().unwrap())
+///   .sum()
+///   .blocking_last(None);
+/// assert_eq!(sum, Ok(6));
+/// ```
+pub fn from_stream<St, Sp>(
+  stream: St,
+  spawner: Sp,
+) -> ObservableBase<StreamEmitter<St, Sp>>
+where
+  St: Stream + Send + 'static,
+  Sp: Spawn,
+{
+  ObservableBase::new(StreamEmitter { stream, spawner })
+}
+
+/// Creates an Observable from a `stream` of `Result`s like
+/// [`from_stream`]. But `Ok` items are emitted as values, and the first `Err`
+/// item is emitted as an error, which ends the subscription.
+pub fn try_from_stream<St, Sp>(
+  stream: St,
+  spawner: Sp,
+) -> ObservableBase<TryStreamEmitter<St, Sp>>
+where
+  St: TryStream + Send + 'static,
+  Sp: Spawn,
+{
+  ObservableBase::new(TryStreamEmitter { stream, spawner })
+}
+
+#[derive(Clone)]
+pub struct StreamEmitter<St, Sp> {
+  stream: St,
+  spawner: Sp,
+}
+
+impl<St: Stream, Sp> Emitter for StreamEmitter<St, Sp> {
+  type Item = St::Item;
+  type Err = ();
+}
+
+impl<St, Sp> SharedEmitter for StreamEmitter<St, Sp>
+where
+  St: Stream + Send + 'static,
+  St::Item: Send + 'static,
+  Sp: Spawn,
+{
+  fn emit<O>(self, subscriber: Subscriber<O, SharedSubscription>)
+  where
+    O: Observer<Self::Item, Self::Err> + Send + Sync + 'static,
+  {
+    spawn_forward(self.stream.map(Ok), self.spawner, subscriber)
+  }
+}
+
+#[derive(Clone)]
+pub struct TryStreamEmitter<St, Sp> {
+  stream: St,
+  spawner: Sp,
+}
+
+impl<St: TryStream, Sp> Emitter for TryStreamEmitter<St, Sp> {
+  type Item = St::Ok;
+  type Err = St::Error;
+}
+
+impl<St, Sp> SharedEmitter for TryStreamEmitter<St, Sp>
+where
+  St: TryStream + Send + 'static,
+  St::Ok: Send + 'static,
+  St::Error: Send + 'static,
+  Sp: Spawn,
+{
+  fn emit<O>(self, subscriber: Subscriber<O, SharedSubscription>)
+  where
+    O: Observer<Self::Item, Self::Err> + Send + Sync + 'static,
+  {
+    spawn_forward(self.stream.into_stream(), self.spawner, subscriber)
+  }
+}
+
+fn spawn_forward<St, Sp, O, Item, Err>(
+  stream: St,
+  mut spawner: Sp,
+  mut subscriber: Subscriber<O, SharedSubscription>,
+) where
+  St: Stream<Item = Result<Item, Err>> + Send + 'static,
+  Item: Send + 'static,
+  Err: Send + 'static,
+  Sp: Spawn,
+  O: Observer<Item, Err> + Send + Sync + 'static,
+{
+  let mut subscription = subscriber.subscription.clone();
+  let c_subscription = subscription.clone();
+  // `None` marks the end of the stream. A stream that is always ready could
+  // be polled forever before the task gets cancelled, so every item checks
+  // the subscription on its own.
+  let f = stream
+    .map(Some)
+    .chain(stream::once(future::ready(None)))
+    .take_while(move |_| future::ready(!c_subscription.is_closed()))
+    .for_each(move |item| {
+      match item {
+        Some(Ok(v)) => subscriber.next(v),
+        Some(Err(err)) => subscriber.error(err),
+        None => subscriber.complete(),
+      }
+      future::ready(())
+    });
+  let handle = spawner
+    .spawn_with_handle(f)
+    .expect("spawn future for a stream failed");
+  subscription.add(SpawnHandle::new(handle));
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use futures::channel::mpsc;
+  use futures::executor::{LocalPool, ThreadPool};
+  use futures::stream;
+
+  fn pool() -> ThreadPool { ThreadPool::new().unwrap() }
+
+  #[test]
+  fn values_and_completion() {
+    let values: Vec<Result<_, ()>> =
+      observable::from_stream(stream::iter(0..3), pool())
+        .blocking_iter()
+        .collect();
+    assert_eq!(values, vec![Ok(0), Ok(1), Ok(2)]);
+  }
+
+  #[test]
+  fn try_stream_error() {
+    let items = vec![Ok(1), Err("oops"), Ok(2)];
+    let values: Vec<_> =
+      observable::try_from_stream(stream::iter(items), pool())
+        .blocking_iter()
+        .collect();
+    assert_eq!(values, vec![Ok(1), Err("oops")]);
+  }
+
+  #[test]
+  fn channel_receiver() {
+    let (mut tx, rx) = mpsc::unbounded();
+    let values = observable::from_stream(rx, pool()).blocking_iter();
+    tx.start_send(1).unwrap();
+    tx.start_send(2).unwrap();
+    drop(tx);
+    assert_eq!(values.collect::<Vec<Result<_, ()>>>(), vec![Ok(1), Ok(2)]);
+  }
+
+  #[test]
+  fn unsubscribe_cancels() {
+    // Never ends on its own.
+    let sum = observable::from_stream(stream::repeat(1), pool())
+      .take(5)
+      .sum()
+      .blocking_last(None);
+    assert_eq!(sum, Ok(5));
+
+    let mut local_pool = LocalPool::new();
+    let (mut tx, rx) = mpsc::unbounded();
+    let mut subscription = observable::from_stream(rx, local_pool.spawner())
+      .to_shared()
+      .subscribe(|_| {});
+    local_pool.run_until_stalled();
+    assert!(tx.start_send(1).is_ok());
+    subscription.unsubscribe();
+    local_pool.run_until_stalled();
+    // The receiver was dropped with the cancelled task.
+    assert!(tx.start_send(1).is_err());
+  }
+}
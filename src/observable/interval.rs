@@ -1,7 +1,8 @@
 use crate::observable::from_future::DEFAULT_RUNTIME;
 use crate::prelude::*;
+use futures::future::RemoteHandle;
 use futures::prelude::*;
-use futures::{future::RemoteHandle, task::SpawnExt};
+use futures::task::{LocalSpawn, LocalSpawnExt, SpawnExt};
 use futures_timer::Interval;
 use std::time::{Duration, Instant};
 
@@ -23,6 +24,75 @@ pub fn interval_at(
   ObservableBase::new(IntervalEmitter { dur, at })
 }
 
+/// Creates an observable like [`interval`], but whose values are emitted by
+/// the single-threaded executor behind `spawner`, such as a
+/// `futures::executor::LocalPool`.
+///
+/// The observer needn't be `Send`, and is always called on the thread which
+/// runs the executor.
+///
+/// `spawner` is a `LocalSpawn`, such as a
+/// [`LocalScheduler`](crate::scheduler::LocalScheduler), and the ticks wait
+/// on the wall clock. To tick on any
+/// [`LocalSchedulerLike`](crate::scheduler::LocalSchedulerLike) instead, use
+/// [`timer_periodic`](crate::observable::timer_periodic).
+///
+/// # Example
+///
+/// ```
+/// use futures::executor::LocalPool;
+/// use rxrust::prelude::*;
+/// use std::{cell::RefCell, rc::Rc, time::Duration};
+///
+/// let mut pool = LocalPool::new();
+/// let values = Rc::new(RefCell::new(vec![]));
+/// let c_values = values.clone();
+/// observable::local_interval(Duration::from_millis(1), pool.spawner())
+///   .take(3)
+///   .subscribe(move |v| c_values.borrow_mut().push(v));
+/// pool.run();
+/// assert_eq!(*values.borrow(), vec![0, 1, 2]);
+/// ```
+pub fn local_interval<Sp>(
+  dur: Duration,
+  spawner: Sp,
+) -> ObservableBase<LocalIntervalEmitter<Sp>>
+where
+  Sp: LocalSpawn,
+{
+  local_interval_at(Instant::now() + dur, dur, spawner)
+}
+
+/// Creates an observable like [`interval_at`], but whose values are emitted
+/// by the single-threaded executor behind `spawner`, like
+/// [`local_interval`].
+pub fn local_interval_at<Sp>(
+  at: Instant,
+  dur: Duration,
+  spawner: Sp,
+) -> ObservableBase<LocalIntervalEmitter<Sp>>
+where
+  Sp: LocalSpawn,
+{
+  ObservableBase::new(LocalIntervalEmitter { dur, at, spawner })
+}
+
+fn interval_future<O>(
+  at: Instant,
+  dur: Duration,
+  mut observer: O,
+) -> impl Future<Output = ()>
+where
+  O: Observer<usize, ()>,
+{
+  let mut number = 0;
+  Interval::new_at(at, dur).for_each(move |_| {
+    observer.next(number);
+    number += 1;
+    future::ready(())
+  })
+}
+
 #[derive(Clone)]
 pub struct IntervalEmitter {
   dur: Duration,
@@ -39,15 +109,10 @@ impl SharedEmitter for IntervalEmitter {
     O: Observer<Self::Item, Self::Err> + Send + Sync + 'static,
   {
     let Subscriber {
-      mut observer,
+      observer,
       mut subscription,
     } = subscriber;
-    let mut number = 0;
-    let f = Interval::new_at(self.at, self.dur).for_each(move |_| {
-      observer.next(number);
-      number += 1;
-      future::ready(())
-    });
+    let f = interval_future(self.at, self.dur, observer);
     let handle = DEFAULT_RUNTIME
       .lock()
       .unwrap()
@@ -58,6 +123,40 @@ impl SharedEmitter for IntervalEmitter {
   }
 }
 
+#[derive(Clone)]
+pub struct LocalIntervalEmitter<Sp> {
+  dur: Duration,
+  at: Instant,
+  spawner: Sp,
+}
+
+impl<Sp> Emitter for LocalIntervalEmitter<Sp> {
+  type Item = usize;
+  type Err = ();
+}
+
+impl<Sp> LocalEmitter<'static> for LocalIntervalEmitter<Sp>
+where
+  Sp: LocalSpawn,
+{
+  fn emit<O>(mut self, subscriber: Subscriber<O, LocalSubscription>)
+  where
+    O: Observer<Self::Item, Self::Err> + 'static,
+  {
+    let Subscriber {
+      observer,
+      mut subscription,
+    } = subscriber;
+    let f = interval_future(self.at, self.dur, observer);
+    let handle = self
+      .spawner
+      .spawn_local_with_handle(f)
+      .expect("spawn future for an interval failed");
+
+    subscription.add(SpawnHandle::new(handle));
+  }
+}
+
 pub struct SpawnHandle<T>(Option<RemoteHandle<T>>);
 
 impl<T> SpawnHandle<T> {
@@ -97,6 +196,25 @@ fn smoke() {
   assert_eq!(*c_seconds.lock().unwrap(), 5);
 }
 
+#[test]
+fn local() {
+  use futures::executor::LocalPool;
+  use std::{cell::RefCell, rc::Rc};
+  let mut pool = LocalPool::new();
+  let values = Rc::new(RefCell::new(vec![]));
+  let c_values = values.clone();
+  let mut subscription =
+    local_interval(Duration::from_millis(1), pool.spawner())
+      .subscribe(move |v| c_values.borrow_mut().push(v));
+  while values.borrow().len() < 3 {
+    pool.try_run_one();
+  }
+  subscription.unsubscribe();
+  // The interval is cancelled, so the pool has nothing left to wait for.
+  pool.run();
+  assert_eq!(*values.borrow(), vec![0, 1, 2]);
+}
+
 #[test]
 fn smoke_fork() {
   interval(Duration::from_millis(10))
@@ -0,0 +1,302 @@
+use crate::prelude::*;
+use futures::stream::Stream;
+use futures::task::{Context, Poll, Waker};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// A [`Stream`] of the notifications of an Observable.
+///
+/// The Observable is only subscribed to when the stream is polled for the
+/// first time, and is unsubscribed from when the stream is dropped. Every
+/// value is yielded as an `Ok` item. An error is yielded as an `Err` item,
+/// after which the stream ends, just like it ends when the Observable
+/// completes.
+///
+/// Values the stream isn't polled for fast enough are buffered, without any
+/// limit unless [`bounded`](ObservableStream::bounded) is used.
+///
+/// This struct is created by the
+/// [`into_stream`](SharedObservable::into_stream) and
+/// [`into_local_stream`](LocalObservable::into_local_stream) methods.
+pub struct ObservableStream<S, C, U>
+where
+  U: SubscriptionLike,
+{
+  source: Option<S>,
+  state: C,
+  subscription: U,
+}
+
+/// The stream [`into_local_stream`](LocalObservable::into_local_stream)
+/// creates.
+pub type LocalObservableStream<S> = ObservableStream<
+  S,
+  Rc<RefCell<StreamState<<S as Observable>::Item, <S as Observable>::Err>>>,
+  LocalSubscription,
+>;
+
+/// The stream [`into_stream`](SharedObservable::into_stream) creates.
+pub type SharedObservableStream<S> = ObservableStream<
+  S,
+  Arc<Mutex<StreamState<<S as Observable>::Item, <S as Observable>::Err>>>,
+  SharedSubscription,
+>;
+
+impl<S, C, U> ObservableStream<S, C, U>
+where
+  U: SubscriptionLike + Default,
+{
+  pub(crate) fn new(source: S, state: C) -> Self {
+    ObservableStream {
+      source: Some(source),
+      state,
+      subscription: U::default(),
+    }
+  }
+}
+
+impl<S, C, U> ObservableStream<S, C, U>
+where
+  S: Observable,
+  C: StateCell<StreamState<S::Item, S::Err>>,
+  U: SubscriptionLike,
+{
+  /// Buffers at most `capacity` values. When the buffer is full, the oldest
+  /// value is dropped to make room for a new one.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `capacity` is zero.
+  pub fn bounded(self, capacity: usize) -> Self {
+    assert!(capacity > 0, "stream capacity must be non-zero");
+    self.state.modify(|state| state.capacity = Some(capacity));
+    self
+  }
+}
+
+// The source isn't pinned, so the stream can be moved around freely.
+impl<S, C, U: SubscriptionLike> Unpin for ObservableStream<S, C, U> {}
+
+impl<S, C, U: SubscriptionLike> Drop for ObservableStream<S, C, U> {
+  fn drop(&mut self) { self.subscription.unsubscribe() }
+}
+
+#[doc(hidden)]
+macro poll_next_impl() {
+  fn poll_next(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Self::Item>> {
+    let this = self.get_mut();
+    if let Some(source) = this.source.take() {
+      let unsub = source.actual_subscribe(Subscriber {
+        observer: StreamObserver(this.state.clone()),
+        subscription: this.subscription.clone(),
+      });
+      this.subscription.add(unsub);
+    }
+    this.state.modify(|state| state.poll(cx))
+  }
+}
+
+impl<'a, S> Stream for LocalObservableStream<S>
+where
+  S: LocalObservable<'a>,
+  S::Item: 'a,
+  S::Err: 'a,
+{
+  type Item = Result<S::Item, S::Err>;
+  poll_next_impl!();
+}
+
+impl<S> Stream for SharedObservableStream<S>
+where
+  S: SharedObservable,
+  S::Item: Send + Sync + 'static,
+  S::Err: Send + Sync + 'static,
+  S::Unsub: Send + Sync,
+{
+  type Item = Result<S::Item, S::Err>;
+  poll_next_impl!();
+}
+
+/// The values an [`ObservableStream`] hasn't yielded yet.
+pub struct StreamState<Item, Err> {
+  buffer: VecDeque<Result<Item, Err>>,
+  capacity: Option<usize>,
+  done: bool,
+  waker: Option<Waker>,
+}
+
+impl<Item, Err> Default for StreamState<Item, Err> {
+  fn default() -> Self {
+    StreamState {
+      buffer: VecDeque::new(),
+      capacity: None,
+      done: false,
+      waker: None,
+    }
+  }
+}
+
+impl<Item, Err> StreamState<Item, Err> {
+  fn push(&mut self, item: Result<Item, Err>) {
+    if self.capacity == Some(self.buffer.len()) {
+      self.buffer.pop_front();
+    }
+    self.buffer.push_back(item);
+    self.wake();
+  }
+
+  fn finish(&mut self) {
+    self.done = true;
+    self.wake();
+  }
+
+  fn wake(&mut self) {
+    if let Some(waker) = self.waker.take() {
+      waker.wake();
+    }
+  }
+
+  fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Item, Err>>> {
+    match self.buffer.pop_front() {
+      Some(item) => Poll::Ready(Some(item)),
+      None if self.done => Poll::Ready(None),
+      None => {
+        self.waker = Some(cx.waker().clone());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+/// Gives mutable access to a value shared between a stream and the observer
+/// feeding it.
+pub trait StateCell<T>: Clone {
+  fn modify<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R;
+}
+
+impl<T> StateCell<T> for Rc<RefCell<T>> {
+  #[inline]
+  fn modify<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+    f(&mut self.borrow_mut())
+  }
+}
+
+impl<T> StateCell<T> for Arc<Mutex<T>> {
+  #[inline]
+  fn modify<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+    f(&mut self.lock().unwrap())
+  }
+}
+
+pub struct StreamObserver<C>(C);
+
+impl<Item, Err, C> Observer<Item, Err> for StreamObserver<C>
+where
+  C: StateCell<StreamState<Item, Err>>,
+{
+  fn next(&mut self, value: Item) {
+    self.0.modify(|state| state.push(Ok(value)))
+  }
+
+  fn error(&mut self, err: Err) {
+    self.0.modify(|state| {
+      state.push(Err(err));
+      state.finish();
+    })
+  }
+
+  fn complete(&mut self) { self.0.modify(|state| state.finish()) }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use futures::executor::block_on;
+  use futures::prelude::*;
+  use std::time::Duration;
+
+  #[test]
+  fn values_and_completion() {
+    let values: Vec<Result<_, ()>> =
+      block_on(observable::from_iter(0..5).into_stream().collect());
+    assert_eq!(values, vec![Ok(0), Ok(1), Ok(2), Ok(3), Ok(4)]);
+  }
+
+  #[test]
+  fn error_ends_stream() {
+    let mut subject = SharedSubject::new();
+    let mut stream = subject.clone().into_stream();
+    assert_eq!(stream.next().now_or_never(), None);
+    subject.next(1);
+    subject.error("oops");
+    subject.next(2);
+    assert_eq!(block_on(stream.next()), Some(Ok(1)));
+    assert_eq!(block_on(stream.next()), Some(Err("oops")));
+    assert_eq!(block_on(stream.next()), None);
+  }
+
+  #[test]
+  fn subscribe_lazily() {
+    let mut subject = SharedSubject::new();
+    let mut stream = subject.clone().into_stream();
+    subject.next(1);
+    assert_eq!(subject.subscribed_size(), 0);
+    assert_eq!(stream.next().now_or_never(), None);
+    assert_eq!(subject.subscribed_size(), 1);
+    subject.next(2);
+    assert_eq!(stream.next().now_or_never(), Some(Some(Ok::<_, ()>(2))));
+  }
+
+  #[test]
+  fn unsubscribe_on_drop() {
+    let mut subject = SharedSubject::new();
+    let mut stream = subject.clone().into_stream();
+    assert_eq!(
+      stream.next().now_or_never(),
+      None::<Option<Result<i32, ()>>>
+    );
+    drop(stream);
+    subject.next(1);
+    assert_eq!(subject.subscribed_size(), 0);
+  }
+
+  #[test]
+  fn bounded_drops_oldest() {
+    let mut subject = SharedSubject::new();
+    let mut stream = subject.clone().into_stream().bounded(2);
+    assert_eq!(stream.next().now_or_never(), None);
+    (0..5).for_each(|v| subject.next(v));
+    subject.complete();
+    let rest: Vec<Result<_, ()>> = block_on(stream.collect());
+    assert_eq!(rest, vec![Ok(3), Ok(4)]);
+  }
+
+  #[test]
+  fn asynchronous_source() {
+    let values: Vec<_> = block_on(
+      observable::interval(Duration::from_millis(1))
+        .into_stream()
+        .take(3)
+        .collect(),
+    );
+    assert_eq!(values, vec![Ok(0), Ok(1), Ok(2)]);
+  }
+
+  #[test]
+  fn local_stream() {
+    let data = [1, 2, 3];
+    let sum = block_on(
+      observable::from_iter(data.iter())
+        .map(|v| v * 10)
+        .into_local_stream()
+        .fold(0, |acc, v: Result<_, ()>| future::ready(acc + v.unwrap())),
+    );
+    assert_eq!(sum, 60);
+  }
+}
@@ -0,0 +1,421 @@
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// Creates an Observable that mirrors the first of `sources` to emit any
+/// notification, whether a value, an error or completion.
+///
+/// All sources are subscribed to in order. As soon as one of them notifies,
+/// it wins the race and all other sources are unsubscribed from. A source
+/// that notifies while being subscribed to wins before the next ones are
+/// even subscribed to. If `sources` is empty, the returned Observable
+/// completes immediately.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+///
+/// let mut emitted = vec![];
+/// {
+///   let mut fast = Subject::new();
+///   let mut slow = Subject::new();
+///   observable::race(vec![slow.clone(), fast.clone()])
+///     .subscribe(|v| emitted.push(v));
+///
+///   fast.next("fast");
+///   slow.next("slow");
+///   fast.next("fast again");
+/// }
+/// assert_eq!(emitted, vec!["fast", "fast again"]);
+/// ```
+pub fn race<I>(sources: I) -> RaceOp<I::Item>
+where
+  I: IntoIterator,
+  I::Item: Observable,
+{
+  RaceOp {
+    sources: sources.into_iter().collect(),
+  }
+}
+
+/// An Observable that mirrors the first of its sources to notify.
+///
+/// This struct is created by the [`race`] function.
+#[derive(Clone)]
+pub struct RaceOp<S> {
+  pub(crate) sources: Vec<S>,
+}
+
+/// An Observable that mirrors whichever of two Observables notifies first.
+///
+/// This struct is created by the [`race_with`](Observable::race_with)
+/// method.
+#[derive(Clone)]
+pub struct RaceWithOp<A, B> {
+  pub(crate) a: A,
+  pub(crate) b: B,
+}
+
+#[doc(hidden)]
+macro subscribe_racer(
+  $subscription:ty, $source:expr, $index:expr,
+  $observer:ident, $gate:ident, $downstream:ident) {{
+  let mut racer = <$subscription>::default();
+  $gate.add_racer(racer.clone());
+  $downstream.add(racer.clone());
+  racer.add($source.actual_subscribe(Subscriber {
+    observer: RaceObserver {
+      observer: $observer.clone(),
+      gate: $gate.clone(),
+      subscription: $downstream.clone(),
+      index: $index,
+    },
+    subscription: racer.clone(),
+  }));
+}}
+
+#[doc(hidden)]
+macro observable_impl(
+  $subscription:ty, $sharer:path, $mutability_enabler:path,
+  $($marker:ident +)* $lf: lifetime)
+{
+  type Unsub = $subscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription;
+    let mut observer = $sharer($mutability_enabler(subscriber.observer));
+    if self.sources.is_empty() {
+      observer.complete();
+      subscription.unsubscribe();
+      return subscription;
+    }
+    let gate = $sharer($mutability_enabler(RaceState::default()));
+    for (index, source) in self.sources.into_iter().enumerate() {
+      if gate.has_winner() {
+        break;
+      }
+      subscribe_racer!(
+        $subscription, source, index, observer, gate, subscription
+      );
+    }
+    subscription
+  }
+}
+
+#[doc(hidden)]
+macro race_with_observable_impl(
+  $subscription:ty, $sharer:path, $mutability_enabler:path,
+  $($marker:ident +)* $lf: lifetime)
+{
+  type Unsub = $subscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription;
+    let observer = $sharer($mutability_enabler(subscriber.observer));
+    let gate = $sharer($mutability_enabler(RaceState::default()));
+    subscribe_racer!($subscription, self.a, 0, observer, gate, subscription);
+    if !gate.has_winner() {
+      subscribe_racer!($subscription, self.b, 1, observer, gate, subscription);
+    }
+    subscription
+  }
+}
+
+impl<S> Observable for RaceOp<S>
+where
+  S: Observable,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S> LocalObservable<'a> for RaceOp<S>
+where
+  S: LocalObservable<'a>,
+  S::Item: 'a,
+  S::Err: 'a,
+{
+  observable_impl!(LocalSubscription, Rc::new, RefCell::new, 'a);
+}
+
+impl<S> SharedObservable for RaceOp<S>
+where
+  S: SharedObservable,
+  S::Item: Send + Sync + 'static,
+  S::Err: Send + Sync + 'static,
+  S::Unsub: Send + Sync,
+{
+  observable_impl!(
+    SharedSubscription,
+    Arc::new,
+    Mutex::new,
+    Send + Sync + 'static
+  );
+}
+
+impl<A, B> Observable for RaceWithOp<A, B>
+where
+  A: Observable,
+  B: Observable<Item = A::Item, Err = A::Err>,
+{
+  type Item = A::Item;
+  type Err = A::Err;
+}
+
+impl<'a, A, B> LocalObservable<'a> for RaceWithOp<A, B>
+where
+  A: LocalObservable<'a>,
+  A::Item: 'a,
+  A::Err: 'a,
+  B: LocalObservable<'a, Item = A::Item, Err = A::Err>,
+{
+  race_with_observable_impl!(LocalSubscription, Rc::new, RefCell::new, 'a);
+}
+
+impl<A, B> SharedObservable for RaceWithOp<A, B>
+where
+  A: SharedObservable,
+  A::Item: Send + Sync + 'static,
+  A::Err: Send + Sync + 'static,
+  A::Unsub: Send + Sync,
+  B: SharedObservable<Item = A::Item, Err = A::Err>,
+  B::Unsub: Send + Sync,
+{
+  race_with_observable_impl!(
+    SharedSubscription,
+    Arc::new,
+    Mutex::new,
+    Send + Sync + 'static
+  );
+}
+
+struct RaceState<U> {
+  winner: Option<usize>,
+  // The subscriptions of all racers, indexed like the sources.
+  racers: Vec<U>,
+}
+
+impl<U> Default for RaceState<U> {
+  fn default() -> Self {
+    RaceState {
+      winner: None,
+      racers: vec![],
+    }
+  }
+}
+
+impl<U> RaceState<U> {
+  /// Returns whether the racer at `index` may notify, and the losers to
+  /// unsubscribe from if it just won the race.
+  fn enter(&mut self, index: usize) -> (bool, Vec<U>) {
+    match self.winner {
+      Some(winner) => (winner == index, vec![]),
+      None => {
+        self.winner = Some(index);
+        let mut losers = std::mem::take(&mut self.racers);
+        losers.remove(index);
+        (true, losers)
+      }
+    }
+  }
+}
+
+trait RaceGate {
+  type Racer: SubscriptionLike;
+  fn add_racer(&self, racer: Self::Racer);
+  fn has_winner(&self) -> bool;
+  fn enter(&self, index: usize) -> (bool, Vec<Self::Racer>);
+
+  fn pass(&self, index: usize) -> bool {
+    // The gate is released by now, a loser's teardown may notify again.
+    let (pass, losers) = self.enter(index);
+    losers.into_iter().for_each(|mut loser| loser.unsubscribe());
+    pass
+  }
+}
+
+impl<U: SubscriptionLike> RaceGate for Rc<RefCell<RaceState<U>>> {
+  type Racer = U;
+  fn add_racer(&self, racer: U) { self.borrow_mut().racers.push(racer); }
+  fn has_winner(&self) -> bool { self.borrow().winner.is_some() }
+  fn enter(&self, index: usize) -> (bool, Vec<U>) {
+    self.borrow_mut().enter(index)
+  }
+}
+
+impl<U: SubscriptionLike> RaceGate for Arc<Mutex<RaceState<U>>> {
+  type Racer = U;
+  fn add_racer(&self, racer: U) { self.lock().unwrap().racers.push(racer); }
+  fn has_winner(&self) -> bool { self.lock().unwrap().winner.is_some() }
+  fn enter(&self, index: usize) -> (bool, Vec<U>) {
+    self.lock().unwrap().enter(index)
+  }
+}
+
+struct RaceObserver<O, G, U> {
+  observer: O,
+  gate: G,
+  subscription: U,
+  index: usize,
+}
+
+impl<O, G, U, Item, Err> Observer<Item, Err> for RaceObserver<O, G, U>
+where
+  O: Observer<Item, Err>,
+  G: RaceGate,
+  U: SubscriptionLike,
+{
+  fn next(&mut self, value: Item) {
+    if self.gate.pass(self.index) {
+      self.observer.next(value);
+    }
+  }
+
+  fn error(&mut self, err: Err) {
+    if self.gate.pass(self.index) {
+      self.observer.error(err);
+      self.subscription.unsubscribe();
+    }
+  }
+
+  fn complete(&mut self) {
+    if self.gate.pass(self.index) {
+      self.observer.complete();
+      self.subscription.unsubscribe();
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::ops::box_it::LocalBoxOp;
+  use crate::prelude::*;
+  use std::cell::RefCell;
+  use std::rc::Rc;
+  use std::sync::{Arc, Mutex};
+  use std::time::Duration;
+
+  #[test]
+  fn first_to_emit_wins() {
+    let emitted = RefCell::new(vec![]);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    let mut c = LocalSubject::new();
+    observable::race(vec![a.clone(), b.clone(), c.clone()])
+      .subscribe(|v| emitted.borrow_mut().push(v));
+    assert_eq!(a.subscribed_size(), 1);
+    assert_eq!(c.subscribed_size(), 1);
+
+    b.next(1);
+    a.next(2);
+    c.next(3);
+    b.next(4);
+    assert_eq!(*emitted.borrow(), vec![1, 4]);
+    assert_eq!(a.subscribed_size(), 0);
+    assert_eq!(c.subscribed_size(), 0);
+  }
+
+  #[test]
+  fn completion_wins_too() {
+    let mut completed = false;
+    let mut next_count = 0;
+    {
+      let mut a = LocalSubject::new();
+      let mut b = LocalSubject::new();
+      a.clone()
+        .race_with(b.clone())
+        .subscribe_complete(|_: i32| next_count += 1, || completed = true);
+      a.complete();
+      b.next(1);
+    }
+    assert!(completed);
+    assert_eq!(next_count, 0);
+  }
+
+  #[test]
+  fn synchronous_source_wins_before_others_subscribed() {
+    let emitted = RefCell::new(vec![]);
+    let mut subject = LocalSubject::new();
+    observable::from_iter(0..3)
+      .race_with(subject.clone())
+      .subscribe(|v| emitted.borrow_mut().push(v));
+    subject.next(100);
+    assert_eq!(subject.subscribed_size(), 0);
+    assert_eq!(*emitted.borrow(), vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn loser_teardown_notifies() {
+    let emitted = Rc::new(RefCell::new(vec![]));
+    let c_emitted = emitted.clone();
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    let mut c = LocalSubject::new();
+    let mut c_c = c.clone();
+    let sources: Vec<LocalBoxOp<'_, i32, ()>> = vec![
+      a.clone().box_it(),
+      b.clone().on_unsubscribe(move || c_c.next(3)).box_it(),
+      c.clone().box_it(),
+    ];
+    observable::race(sources)
+      .subscribe(move |v| c_emitted.borrow_mut().push(v));
+    a.next(1);
+    b.next(4);
+    c.next(5);
+    a.next(2);
+    assert_eq!(*emitted.borrow(), vec![1, 2]);
+  }
+
+  #[test]
+  fn empty_sources_complete() {
+    let mut completed = false;
+    observable::race(Vec::<ObservableBase<EmptyEmitter<i32>>>::new())
+      .subscribe_complete(|_| {}, || completed = true);
+    assert!(completed);
+  }
+
+  #[test]
+  fn unsubscribe_losing_interval() {
+    let emitted = Arc::new(Mutex::new(vec![]));
+    let c_emitted = emitted.clone();
+    let clock = VirtualClock::default();
+    let scheduler = ManualScheduler::with_clock(clock.clone());
+    let mut fast = SharedSubject::new();
+    let mut subscription = observable::timer_periodic(
+      Duration::from_secs(1),
+      Duration::from_secs(1),
+      scheduler.clone(),
+    )
+    .map(|v| v + 100)
+    .race_with(fast.clone())
+    .to_shared()
+    .subscribe(move |v| c_emitted.lock().unwrap().push(v));
+    fast.next(0);
+    // The losing timer doesn't wait for its first tick anymore.
+    assert_eq!(scheduler.next_due(), None);
+    clock.advance(Duration::from_secs(3));
+    scheduler.run_pending(clock.now());
+    fast.next(1);
+    subscription.unsubscribe();
+    assert_eq!(*emitted.lock().unwrap(), vec![0, 1]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let race = observable::race(vec![
+      observable::from_iter(0..10),
+      observable::from_iter(10..20),
+    ]);
+    race.clone().to_shared().subscribe(|_| {});
+    race
+      .race_with(observable::of(1))
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
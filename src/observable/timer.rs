@@ -0,0 +1,292 @@
+use crate::prelude::*;
+use crate::scheduler::{LocalSchedulerLike, Scheduler};
+use std::time::{Duration, Instant};
+
+/// Creates an Observable that emits `0` after `delay` and completes.
+///
+/// The delay starts when the Observable is subscribed to, and is waited for
+/// on `scheduler`, which also determines the thread the value is emitted on.
+/// With a [`Scheduler`] the Observable is a shared one, with a
+/// [`LocalSchedulerLike`] such as a [`LocalScheduler`] it's a local one.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::time::Duration;
+///
+/// let v = observable::timer(Duration::from_millis(1), Schedulers::ThreadPool)
+///   .blocking_last(None);
+/// assert_eq!(v, Ok(0));
+/// ```
+pub fn timer<SD>(
+  delay: Duration,
+  scheduler: SD,
+) -> ObservableBase<TimerEmitter<SD>> {
+  ObservableBase::new(TimerEmitter {
+    due: Due::After(delay),
+    period: None,
+    scheduler,
+  })
+}
+
+/// Creates an Observable that emits `0` at `at` and completes.
+///
+/// The time is compared to [`Scheduler::now`], so with a virtual-time
+/// scheduler it has to be a virtual time too. If `at` is already over, `0` is
+/// emitted as soon as possible.
+pub fn timer_at<SD>(
+  at: Instant,
+  scheduler: SD,
+) -> ObservableBase<TimerEmitter<SD>> {
+  ObservableBase::new(TimerEmitter {
+    due: Due::At(at),
+    period: None,
+    scheduler,
+  })
+}
+
+/// Creates an Observable that emits `0` after `initial_delay`, and then an
+/// increasing number every `period`. It never completes.
+///
+/// Unlike [`interval`](crate::observable::interval), the values are timed by
+/// `scheduler`. Every value is due a whole number of periods after the first
+/// one, so late values don't delay the following ones.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::time::Duration;
+///
+/// let values = observable::timer_periodic(
+///   Duration::from_millis(5),
+///   Duration::from_millis(1),
+///   Schedulers::ThreadPool,
+/// )
+/// .take(3)
+/// .to_vec()
+/// .blocking_last(None);
+/// assert_eq!(values, Ok(vec![0, 1, 2]));
+/// ```
+pub fn timer_periodic<SD>(
+  initial_delay: Duration,
+  period: Duration,
+  scheduler: SD,
+) -> ObservableBase<TimerEmitter<SD>> {
+  ObservableBase::new(TimerEmitter {
+    due: Due::After(initial_delay),
+    period: Some(period),
+    scheduler,
+  })
+}
+
+#[derive(Clone, Copy)]
+enum Due {
+  After(Duration),
+  At(Instant),
+}
+
+#[derive(Clone)]
+pub struct TimerEmitter<SD> {
+  due: Due,
+  period: Option<Duration>,
+  scheduler: SD,
+}
+
+impl<SD> Emitter for TimerEmitter<SD> {
+  type Item = usize;
+  type Err = ();
+}
+
+impl<SD> SharedEmitter for TimerEmitter<SD>
+where
+  SD: Scheduler + Clone + Send + 'static,
+{
+  fn emit<O>(self, subscriber: Subscriber<O, SharedSubscription>)
+  where
+    O: Observer<Self::Item, Self::Err> + Send + Sync + 'static,
+  {
+    let at = due_at(self.due, self.scheduler.now());
+    schedule_tick(self.scheduler, at, self.period, 0, subscriber);
+  }
+}
+
+impl<SD> LocalEmitter<'static> for TimerEmitter<SD>
+where
+  SD: LocalSchedulerLike + Clone + 'static,
+{
+  fn emit<O>(self, subscriber: Subscriber<O, LocalSubscription>)
+  where
+    O: Observer<Self::Item, Self::Err> + 'static,
+  {
+    let at = due_at(self.due, self.scheduler.now());
+    schedule_local_tick(self.scheduler, at, self.period, 0, subscriber);
+  }
+}
+
+fn due_at(due: Due, now: Instant) -> Instant {
+  match due {
+    Due::After(delay) => now + delay,
+    Due::At(at) => at,
+  }
+}
+
+#[doc(hidden)]
+macro schedule_tick_fn(
+  $name: ident, $subscription: ty, {$($sd_bounds: tt)*}, {$($o_bounds: tt)*})
+{
+  fn $name<SD, O>(
+    scheduler: SD,
+    at: Instant,
+    period: Option<Duration>,
+    number: usize,
+    mut subscriber: Subscriber<O, $subscription>,
+  ) where
+    SD: $($sd_bounds)*,
+    O: Observer<usize, ()> + $($o_bounds)*,
+  {
+    let now = scheduler.now();
+    let delay = if at > now {
+      at - now
+    } else {
+      Duration::from_secs(0)
+    };
+    // Every tick gets its own subscription, which is added before the task is
+    // scheduled, so a task that runs at once can still remove it again.
+    let mut tick = <$subscription>::default();
+    subscriber.subscription.add(tick.clone());
+    let c_tick = tick.clone();
+    let c_scheduler = scheduler.clone();
+    let handle = scheduler.schedule(
+      move |_, mut subscriber: Subscriber<O, $subscription>| {
+        subscriber.subscription.remove(&c_tick);
+        subscriber.next(number);
+        match period {
+          Some(period) => $name(
+            c_scheduler,
+            at + period,
+            Some(period),
+            number + 1,
+            subscriber,
+          ),
+          None => subscriber.complete(),
+        }
+      },
+      Some(delay),
+      subscriber,
+    );
+    tick.add(handle);
+  }
+}
+
+schedule_tick_fn!(
+  schedule_tick,
+  SharedSubscription,
+  { Scheduler + Clone + Send + 'static },
+  { Send + Sync + 'static }
+);
+schedule_tick_fn!(
+  schedule_local_tick,
+  LocalSubscription,
+  { LocalSchedulerLike + Clone + 'static },
+  { 'static }
+);
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use crate::scheduler::Scheduler;
+  use std::sync::{Arc, Mutex};
+  use std::time::Duration;
+
+  type Values = Arc<Mutex<Vec<usize>>>;
+
+  fn virtual_scheduler() -> (VirtualClock, ManualScheduler<VirtualClock>) {
+    let clock = VirtualClock::default();
+    (clock.clone(), ManualScheduler::with_clock(clock))
+  }
+
+  /// Advances `clock` and runs all the tasks due by then, including those
+  /// scheduled by the tasks.
+  fn advance(
+    clock: &VirtualClock,
+    scheduler: &ManualScheduler<VirtualClock>,
+    dur: Duration,
+  ) {
+    clock.advance(dur);
+    while scheduler.run_pending(clock.now()) > 0 {}
+  }
+
+  fn collect(
+    o: ObservableBase<impl SharedEmitter<Item = usize, Err = ()>>,
+  ) -> (Values, Arc<Mutex<bool>>, SharedSubscription) {
+    let values = Arc::new(Mutex::new(vec![]));
+    let completed = Arc::new(Mutex::new(false));
+    let c_values = values.clone();
+    let c_completed = completed.clone();
+    let subscription = o
+      .to_shared()
+      .subscribe_complete(
+        move |v| c_values.lock().unwrap().push(v),
+        move || *c_completed.lock().unwrap() = true,
+      )
+      .0;
+    (values, completed, subscription)
+  }
+
+  #[test]
+  fn timer() {
+    let (clock, scheduler) = virtual_scheduler();
+    let (values, completed, _) =
+      collect(observable::timer(Duration::from_secs(5), scheduler.clone()));
+    advance(&clock, &scheduler, Duration::from_secs(4));
+    assert!(values.lock().unwrap().is_empty());
+    advance(&clock, &scheduler, Duration::from_secs(1));
+    assert_eq!(*values.lock().unwrap(), vec![0]);
+    assert!(*completed.lock().unwrap());
+  }
+
+  #[test]
+  fn timer_at() {
+    let (clock, scheduler) = virtual_scheduler();
+    let at = scheduler.now() + Duration::from_secs(3);
+    let (values, completed, _) =
+      collect(observable::timer_at(at, scheduler.clone()));
+    advance(&clock, &scheduler, Duration::from_secs(2));
+    assert!(values.lock().unwrap().is_empty());
+    advance(&clock, &scheduler, Duration::from_secs(1));
+    assert_eq!(*values.lock().unwrap(), vec![0]);
+    assert!(*completed.lock().unwrap());
+  }
+
+  #[test]
+  fn periodic() {
+    let (clock, scheduler) = virtual_scheduler();
+    let (values, completed, mut subscription) =
+      collect(observable::timer_periodic(
+        Duration::from_secs(10),
+        Duration::from_secs(1),
+        scheduler.clone(),
+      ));
+    advance(&clock, &scheduler, Duration::from_secs(10));
+    assert_eq!(*values.lock().unwrap(), vec![0]);
+    advance(&clock, &scheduler, Duration::from_millis(2500));
+    assert_eq!(*values.lock().unwrap(), vec![0, 1, 2]);
+    // Only the pending tick is kept.
+    assert_eq!(subscription.teardown_size(), 1);
+
+    subscription.unsubscribe();
+    advance(&clock, &scheduler, Duration::from_secs(10));
+    assert_eq!(*values.lock().unwrap(), vec![0, 1, 2]);
+    assert!(!*completed.lock().unwrap());
+    assert_eq!(scheduler.next_due(), None);
+  }
+
+  #[test]
+  fn thread_pool() {
+    let v = observable::timer(Duration::from_millis(1), Schedulers::ThreadPool)
+      .blocking_last(None);
+    assert_eq!(v, Ok(0));
+  }
+}
@@ -0,0 +1,186 @@
+use crate::observable::into_stream::{
+  LocalObservableStream, SharedObservableStream,
+};
+use crate::prelude::*;
+use futures::prelude::*;
+use futures::stream::TryStream;
+use futures::task::{Context, Poll};
+use std::fmt::{Display, Formatter};
+use std::pin::Pin;
+
+/// Why an [`ObservableFuture`] resolved without a value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EmptyOrErr<Err> {
+  /// The Observable completed without emitting any value.
+  Empty,
+  /// The Observable emitted an error.
+  Err(Err),
+}
+
+impl<Err: Display> Display for EmptyOrErr<Err> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      EmptyOrErr::Empty => write!(f, "observable completed without a value"),
+      EmptyOrErr::Err(err) => err.fmt(f),
+    }
+  }
+}
+
+impl<Err: std::error::Error> std::error::Error for EmptyOrErr<Err> {}
+
+/// A [`Future`] resolving to the first or the last value of an Observable.
+///
+/// The Observable is subscribed to when the future is polled for the first
+/// time, and unsubscribed from as soon as the future resolves or is dropped.
+/// Only the first value is taken from the Observable, or only the latest one
+/// is kept while waiting for the last, so even a synchronous or long source
+/// doesn't pile up values.
+///
+/// This struct is created by the
+/// [`first_value`](crate::shared::SharedObservable::first_value) and
+/// [`last_value`](crate::shared::SharedObservable::last_value) methods, and
+/// their local counterparts
+/// [`first_local_value`](LocalObservable::first_local_value) and
+/// [`last_local_value`](LocalObservable::last_local_value).
+pub struct ObservableFuture<St, Item> {
+  stream: Option<St>,
+  last: Option<Item>,
+  first: bool,
+}
+
+/// The future [`first_value`](crate::shared::SharedObservable::first_value)
+/// and [`last_value`](crate::shared::SharedObservable::last_value) create.
+pub type SharedObservableFuture<S> =
+  ObservableFuture<SharedObservableStream<S>, <S as Observable>::Item>;
+
+/// The future [`first_local_value`](LocalObservable::first_local_value) and
+/// [`last_local_value`](LocalObservable::last_local_value) create.
+pub type LocalObservableFuture<S> =
+  ObservableFuture<LocalObservableStream<S>, <S as Observable>::Item>;
+
+impl<St, Item> ObservableFuture<St, Item> {
+  pub(crate) fn new(stream: St, first: bool) -> Self {
+    ObservableFuture {
+      stream: Some(stream),
+      last: None,
+      first,
+    }
+  }
+}
+
+// The stream is polled through `Unpin` only, so the future needn't be pinned.
+impl<St, Item> Unpin for ObservableFuture<St, Item> {}
+
+impl<St, Item> Future for ObservableFuture<St, Item>
+where
+  St: TryStream<Ok = Item> + Unpin,
+{
+  type Output = Result<Item, EmptyOrErr<St::Error>>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let this = self.get_mut();
+    let stream = this
+      .stream
+      .as_mut()
+      .expect("observable future polled after completion");
+    let res = loop {
+      match stream.try_poll_next_unpin(cx) {
+        Poll::Ready(Some(Ok(v))) if this.first => break Ok(v),
+        Poll::Ready(Some(Ok(v))) => this.last = Some(v),
+        Poll::Ready(Some(Err(err))) => break Err(EmptyOrErr::Err(err)),
+        Poll::Ready(None) => break this.last.take().ok_or(EmptyOrErr::Empty),
+        Poll::Pending => return Poll::Pending,
+      }
+    };
+    this.stream = None;
+    Poll::Ready(res)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::EmptyOrErr;
+  use crate::prelude::*;
+  use futures::executor::block_on;
+  use futures::prelude::*;
+  use std::time::Duration;
+
+  #[test]
+  fn first_and_last() {
+    let o = observable::from_iter(1..4);
+    assert_eq!(
+      block_on(o.clone().first_value()),
+      Ok::<_, EmptyOrErr<()>>(1)
+    );
+    assert_eq!(block_on(o.clone().last_value()), Ok(3));
+    assert_eq!(block_on(o.to_future()), Ok(3));
+  }
+
+  #[test]
+  fn empty() {
+    let o = observable::empty::<i32>();
+    assert_eq!(
+      block_on(o.clone().first_value()),
+      Err(EmptyOrErr::<()>::Empty)
+    );
+    assert_eq!(block_on(o.last_value()), Err(EmptyOrErr::Empty));
+  }
+
+  #[test]
+  fn error() {
+    let mut subject = SharedSubject::new();
+    let mut future = subject.clone().last_value();
+    assert_eq!((&mut future).now_or_never(), None);
+    subject.next(1);
+    subject.error("oops");
+    assert_eq!(block_on(future), Err(EmptyOrErr::Err("oops")));
+  }
+
+  #[test]
+  fn asynchronous_source() {
+    let first = observable::interval(Duration::from_millis(1))
+      .skip(2)
+      .first_value();
+    assert_eq!(block_on(first), Ok::<_, EmptyOrErr<()>>(2));
+  }
+
+  #[test]
+  fn first_of_infinite_source() {
+    let first = observable::from_iter(0..).first_value();
+    assert_eq!(block_on(first), Ok::<_, EmptyOrErr<()>>(0));
+    let first = observable::from_iter(0..).first_local_value();
+    assert_eq!(block_on(first), Ok::<_, EmptyOrErr<()>>(0));
+  }
+
+  #[test]
+  fn local() {
+    let mut subject = LocalSubject::new();
+    let mut last = subject.clone().to_local_future();
+    assert_eq!((&mut last).now_or_never(), None);
+    subject.next(1);
+    subject.next(2);
+    subject.complete();
+    assert_eq!(block_on(last), Ok::<_, EmptyOrErr<()>>(2));
+    assert_eq!(
+      block_on(observable::empty::<i32>().last_local_value()),
+      Err(EmptyOrErr::<()>::Empty)
+    );
+  }
+
+  #[test]
+  fn unsubscribe_when_resolved_or_dropped() {
+    let mut subject = SharedSubject::new();
+    let mut first = subject.clone().first_value();
+    assert_eq!((&mut first).now_or_never(), None);
+    subject.next(1);
+    assert_eq!(first.now_or_never(), Some(Ok::<_, EmptyOrErr<()>>(1)));
+    subject.next(2);
+    assert_eq!(subject.subscribed_size(), 0);
+
+    let mut last = subject.clone().last_value();
+    assert_eq!((&mut last).now_or_never(), None::<Result<i32, _>>);
+    drop(last);
+    subject.next(3);
+    assert_eq!(subject.subscribed_size(), 0);
+  }
+}
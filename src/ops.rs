@@ -1,36 +1,61 @@
+pub mod all;
+pub mod any;
+pub mod collect;
 pub mod default_if_empty;
 pub mod delay;
+pub mod distinct;
+pub mod distinct_until_changed;
+pub mod end_with;
 pub mod filter;
 pub mod filter_map;
 pub mod finalize;
+pub mod find_index;
 pub mod first;
 pub mod last;
 pub mod map;
 pub mod map_to;
 pub mod merge;
 pub mod observe_on;
+pub mod pairwise;
+pub mod partition;
+pub mod reduce_by;
 pub mod ref_count;
 pub mod sample;
 pub mod scan;
+pub mod sequence_equal;
+pub mod single;
 pub mod skip;
 pub mod skip_last;
+pub mod skip_until;
+pub mod skip_while;
+pub mod sliding;
+pub mod start_with;
+pub mod statistics;
 pub mod subscribe_on;
 pub mod take;
 pub mod take_last;
 pub mod take_until;
 pub mod take_while;
+pub mod tap;
 pub mod throttle_time;
+pub mod time_interval;
+pub mod timestamp;
 pub use filter_map::FilterMap;
 pub mod box_it;
 pub mod zip;
 
+use collect::ToHashMap;
+use filter::FilterOp;
 use last::LastOrOp;
 use map::MapOp;
+use reduce_by::{CountBy, MinMaxBy, ReduceByOp, ToMapBy};
 use scan::ScanOp;
 
 pub type CountOp<Source, Item> =
   ReduceOp<Source, fn(usize, Item) -> usize, usize>;
 pub type SumOp<Source, Item> = ReduceOp<Source, fn(Item, Item) -> Item, Item>;
+/// A `filter` that lets no value through.
+pub type IgnoreElementsOp<Source, Item> = FilterOp<Source, fn(&Item) -> bool>;
 
 // A composition of `scan` followed by `last`
 pub type ReduceOp<Source, BinaryOp, OutputItem> =
@@ -43,6 +68,13 @@ pub type MinMaxOp<Source, Item> = MapOp<
   >,
   fn(Option<Item>) -> Item,
 >;
+pub type MinMaxByOp<Source, Item, Compare> =
+  ReduceByOp<Source, MinMaxBy<Compare, Item>>;
+pub type CountByOp<Source, Key, F> = ReduceByOp<Source, CountBy<F, Key>>;
+pub type ToMapByOp<Source, Item, Key, F> =
+  ReduceByOp<Source, ToMapBy<F, Key, Item>>;
+pub type ToHashMapOp<Source, K, V, FK, FV> =
+  ReduceByOp<Source, ToHashMap<FK, FV, K, V>>;
 
 /// Holds intermediate computations of accumulated values for
 /// [`Observable@Average`] operator, as nominator and denominator respectively.
@@ -422,4 +454,26 @@ mod test {
     let m = observable::from_iter(vec![1., 2.]).average();
     m.to_shared().to_shared().subscribe(|_| {});
   }
+
+  #[test]
+  fn ignore_elements() {
+    let mut next_count = 0;
+    let mut completed = false;
+    observable::from_iter(0..10)
+      .ignore_elements()
+      .subscribe_complete(|_| next_count += 1, || completed = true);
+    assert_eq!(next_count, 0);
+    assert!(completed);
+
+    let mut error = None;
+    observable::throw("oops")
+      .ignore_elements()
+      .subscribe_err(|_: ()| {}, |e| error = Some(e));
+    assert_eq!(error, Some("oops"));
+
+    observable::of(1)
+      .ignore_elements()
+      .to_shared()
+      .subscribe(|_| {});
+  }
 }
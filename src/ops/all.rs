@@ -0,0 +1,159 @@
+use crate::observer::error_proxy_impl;
+use crate::prelude::*;
+
+/// An Observable that emits whether all values of its source satisfy a
+/// predicate.
+///
+/// This struct is created by the [`all`](Observable::all) method, and the
+/// [`is_empty`](Observable::is_empty) shorthand.
+#[derive(Clone)]
+pub struct AllOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) predicate: F,
+}
+
+/// The type returned by [`is_empty`](Observable::is_empty).
+pub type IsEmptyOp<S, Item> = AllOp<S, fn(&Item) -> bool>;
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: AllObserver {
+        observer: subscriber.observer,
+        subscription: subscriber.subscription.clone(),
+        predicate: self.predicate,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, F> Observable for AllOp<S, F>
+where
+  S: Observable,
+  F: FnMut(&S::Item) -> bool,
+{
+  type Item = bool;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for AllOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: FnMut(&S::Item) -> bool + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F> SharedObservable for AllOp<S, F>
+where
+  S: SharedObservable,
+  F: FnMut(&S::Item) -> bool + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct AllObserver<O, U, F> {
+  observer: O,
+  subscription: U,
+  predicate: F,
+}
+
+impl<O, U, F, Item, Err> Observer<Item, Err> for AllObserver<O, U, F>
+where
+  O: Observer<bool, Err>,
+  U: SubscriptionLike,
+  F: FnMut(&Item) -> bool,
+{
+  fn next(&mut self, value: Item) {
+    if !(self.predicate)(&value) {
+      self.observer.next(false);
+      self.observer.complete();
+      self.subscription.unsubscribe();
+    }
+  }
+
+  error_proxy_impl!(Err, observer);
+
+  fn complete(&mut self) {
+    self.observer.next(true);
+    self.observer.complete();
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::cell::RefCell;
+
+  #[test]
+  fn all() {
+    let mut emitted = vec![];
+    let mut completed = 0;
+    observable::from_iter(0..10)
+      .all(|v| *v < 10)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+    observable::from_iter(0..10)
+      .all(|v| *v < 5)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+    observable::empty::<i32>()
+      .all(|_| false)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+
+    assert_eq!(emitted, vec![true, false, true]);
+    assert_eq!(completed, 3);
+  }
+
+  #[test]
+  fn all_short_circuits() {
+    let mut visited = 0;
+    observable::from_iter(0..100)
+      .tap_next(|_| visited += 1)
+      .all(|v| *v < 3)
+      .subscribe(|_| {});
+    assert_eq!(visited, 4);
+  }
+
+  #[test]
+  fn is_empty() {
+    let mut emitted = vec![];
+    observable::empty::<i32>()
+      .is_empty()
+      .subscribe(|v| emitted.push(v));
+    observable::of(1).is_empty().subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![true, false]);
+  }
+
+  #[test]
+  fn is_empty_short_circuits() {
+    let emitted = RefCell::new(vec![]);
+    let mut subject = LocalSubject::new();
+    subject
+      .clone()
+      .is_empty()
+      .subscribe(|v| emitted.borrow_mut().push(v));
+    assert_eq!(subject.subscribed_size(), 1);
+    subject.next(1);
+    subject.next(2);
+    assert_eq!(subject.subscribed_size(), 0);
+    assert_eq!(*emitted.borrow(), vec![false]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let all = observable::from_iter(0..10).all(|v| *v < 5);
+    all.clone().to_shared().subscribe(|_| {});
+    all.to_shared().subscribe(|_| {});
+    observable::from_iter(0..10)
+      .is_empty()
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
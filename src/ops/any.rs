@@ -0,0 +1,217 @@
+use crate::observer::error_proxy_impl;
+use crate::prelude::*;
+
+/// An Observable that emits whether any value of its source satisfies a
+/// predicate.
+///
+/// This struct is created by the [`any`](Observable::any) method.
+#[derive(Clone)]
+pub struct AnyOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) predicate: F,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: AnyObserver {
+        observer: subscriber.observer,
+        subscription: subscriber.subscription.clone(),
+        predicate: self.predicate,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, F> Observable for AnyOp<S, F>
+where
+  S: Observable,
+  F: FnMut(&S::Item) -> bool,
+{
+  type Item = bool;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for AnyOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: FnMut(&S::Item) -> bool + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F> SharedObservable for AnyOp<S, F>
+where
+  S: SharedObservable,
+  F: FnMut(&S::Item) -> bool + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+/// An Observable that emits whether its source emits a given value.
+///
+/// This struct is created by the [`contains`](Observable::contains) method.
+#[derive(Clone)]
+pub struct ContainsOp<S, Item> {
+  pub(crate) source: S,
+  pub(crate) item: Item,
+}
+
+#[doc(hidden)]
+macro contains_observable_impl(
+  $subscription:ty, $($marker:ident +)* $lf: lifetime)
+{
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: AnyObserver {
+        observer: subscriber.observer,
+        subscription: subscriber.subscription.clone(),
+        predicate: equal_to(self.item),
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+fn equal_to<Item: PartialEq>(item: Item) -> impl FnMut(&Item) -> bool {
+  move |v| v == &item
+}
+
+impl<S, Item> Observable for ContainsOp<S, Item>
+where
+  S: Observable<Item = Item>,
+  Item: PartialEq,
+{
+  type Item = bool;
+  type Err = S::Err;
+}
+
+impl<'a, S, Item> LocalObservable<'a> for ContainsOp<S, Item>
+where
+  S: LocalObservable<'a, Item = Item>,
+  Item: PartialEq + 'a,
+{
+  type Unsub = S::Unsub;
+  contains_observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, Item> SharedObservable for ContainsOp<S, Item>
+where
+  S: SharedObservable<Item = Item>,
+  Item: PartialEq + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  contains_observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct AnyObserver<O, U, F> {
+  observer: O,
+  subscription: U,
+  predicate: F,
+}
+
+impl<O, U, F, Item, Err> Observer<Item, Err> for AnyObserver<O, U, F>
+where
+  O: Observer<bool, Err>,
+  U: SubscriptionLike,
+  F: FnMut(&Item) -> bool,
+{
+  fn next(&mut self, value: Item) {
+    if (self.predicate)(&value) {
+      self.observer.next(true);
+      self.observer.complete();
+      self.subscription.unsubscribe();
+    }
+  }
+
+  error_proxy_impl!(Err, observer);
+
+  fn complete(&mut self) {
+    self.observer.next(false);
+    self.observer.complete();
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::cell::RefCell;
+
+  #[test]
+  fn any() {
+    let mut emitted = vec![];
+    let mut completed = 0;
+    observable::from_iter(0..10)
+      .any(|v| *v > 5)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+    observable::from_iter(0..10)
+      .any(|v| *v > 10)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+    observable::empty::<i32>()
+      .any(|_| true)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+
+    assert_eq!(emitted, vec![true, false, false]);
+    assert_eq!(completed, 3);
+  }
+
+  #[test]
+  fn any_short_circuits() {
+    let mut visited = 0;
+    observable::from_iter(0..100)
+      .tap_next(|_| visited += 1)
+      .any(|v| *v == 3)
+      .subscribe(|_| {});
+    assert_eq!(visited, 4);
+  }
+
+  #[test]
+  fn any_unsubscribes_source() {
+    let emitted = RefCell::new(vec![]);
+    let mut subject = LocalSubject::new();
+    let subscription = subject
+      .clone()
+      .any(|v| *v == 2)
+      .subscribe(|v| emitted.borrow_mut().push(v));
+    subject.next(1);
+    assert_eq!(subject.subscribed_size(), 1);
+    subject.next(2);
+    subject.next(3);
+    assert_eq!(subject.subscribed_size(), 0);
+    assert!(subscription.is_closed());
+    assert_eq!(*emitted.borrow(), vec![true]);
+  }
+
+  #[test]
+  fn contains() {
+    let mut emitted = vec![];
+    observable::from_iter(vec!["a", "b", "c"])
+      .contains("b")
+      .subscribe(|v| emitted.push(v));
+    observable::from_iter(vec!["a", "b", "c"])
+      .contains("d")
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![true, false]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let any = observable::from_iter(0..10).any(|v| *v == 5);
+    any.clone().to_shared().subscribe(|_| {});
+    any.to_shared().subscribe(|_| {});
+    let contains = observable::from_iter(0..10).contains(5);
+    contains.clone().to_shared().subscribe(|_| {});
+    contains.to_shared().subscribe(|_| {});
+  }
+}
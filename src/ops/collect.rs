@@ -0,0 +1,211 @@
+use crate::observer::error_proxy_impl;
+use crate::ops::reduce_by::Accumulator;
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Collects all values of the source Observable into a collection, and emits
+/// it once the source completes.
+///
+/// Unlike [`reduce`](Observable::reduce), the collection is extended in place
+/// and never cloned.
+#[derive(Clone)]
+pub struct CollectOp<S, C> {
+  pub(crate) source: S,
+  pub(crate) collection: C,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let subscriber = Subscriber {
+      observer: CollectObserver {
+        observer: subscriber.observer,
+        collection: self.collection,
+      },
+      subscription: subscriber.subscription,
+    };
+    self.source.actual_subscribe(subscriber)
+  }
+}
+
+impl<S, C> Observable for CollectOp<S, C>
+where
+  S: Observable,
+  C: Default + Extend<S::Item>,
+{
+  type Item = C;
+  type Err = S::Err;
+}
+
+impl<'a, S, C> LocalObservable<'a> for CollectOp<S, C>
+where
+  S: LocalObservable<'a>,
+  C: Default + Extend<S::Item> + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, C> SharedObservable for CollectOp<S, C>
+where
+  S: SharedObservable,
+  C: Default + Extend<S::Item> + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct CollectObserver<O, C> {
+  observer: O,
+  collection: C,
+}
+
+impl<Item, Err, O, C> Observer<Item, Err> for CollectObserver<O, C>
+where
+  O: Observer<C, Err>,
+  C: Default + Extend<Item>,
+{
+  #[inline]
+  fn next(&mut self, value: Item) { self.collection.extend(Some(value)) }
+
+  fn complete(&mut self) {
+    self.observer.next(std::mem::take(&mut self.collection));
+    self.observer.complete()
+  }
+
+  error_proxy_impl!(Err, observer);
+}
+
+/// Inserts the key-value pair made of every item into a [`HashMap`], for
+/// [`to_hash_map`](Observable::to_hash_map).
+pub struct ToHashMap<FK, FV, K, V> {
+  key: FK,
+  value: FV,
+  map: HashMap<K, V>,
+}
+
+impl<FK, FV, K, V> ToHashMap<FK, FV, K, V> {
+  pub(crate) fn new(key: FK, value: FV) -> Self {
+    ToHashMap {
+      key,
+      value,
+      map: HashMap::new(),
+    }
+  }
+}
+
+// Only the functions are cloned, an operator is cloned before any item is
+// inserted.
+impl<FK: Clone, FV: Clone, K, V> Clone for ToHashMap<FK, FV, K, V> {
+  fn clone(&self) -> Self {
+    ToHashMap::new(self.key.clone(), self.value.clone())
+  }
+}
+
+impl<Item, FK, FV, K, V> Accumulator<Item> for ToHashMap<FK, FV, K, V>
+where
+  K: Hash + Eq,
+  FK: FnMut(&Item) -> K,
+  FV: FnMut(Item) -> V,
+{
+  type Output = HashMap<K, V>;
+
+  fn accumulate(&mut self, value: Item) {
+    let key = (self.key)(&value);
+    self.map.insert(key, (self.value)(value));
+  }
+
+  #[inline]
+  fn finish(&mut self) -> Option<Self::Output> {
+    Some(std::mem::take(&mut self.map))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::collections::{BTreeSet, HashMap};
+
+  #[test]
+  fn to_vec() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(0..5).to_vec().subscribe_all(
+      |v| emitted.push(v),
+      |_: ()| {},
+      || completed = true,
+    );
+    assert_eq!(emitted, vec![vec![0, 1, 2, 3, 4]]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn to_vec_empty() {
+    let mut emitted = None;
+    observable::empty()
+      .to_vec()
+      .subscribe(|v: Vec<i32>| emitted = Some(v));
+    assert_eq!(emitted, Some(vec![]));
+  }
+
+  #[test]
+  fn error_discards_collection() {
+    let mut emitted = 0;
+    let mut error = None;
+    {
+      let mut subject = LocalSubject::new();
+      subject
+        .clone()
+        .to_vec()
+        .subscribe_err(|_| emitted += 1, |e| error = Some(e));
+      subject.next(1);
+      subject.error("oops");
+    }
+    assert_eq!(emitted, 0);
+    assert_eq!(error, Some("oops"));
+  }
+
+  #[test]
+  fn collect() {
+    let mut set = BTreeSet::new();
+    observable::from_iter(vec![3, 1, 3, 2])
+      .collect::<BTreeSet<_>>()
+      .subscribe(|v| set = v);
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    let mut s = String::new();
+    observable::from_iter(vec!['r', 'x'])
+      .collect::<String>()
+      .subscribe(|v| s = v);
+    assert_eq!(s, "rx");
+  }
+
+  #[test]
+  fn to_hash_map() {
+    let mut map = HashMap::new();
+    observable::from_iter(vec!["a", "bb", "cc"])
+      .to_hash_map(|s| s.len(), |s| s.to_uppercase())
+      .subscribe(|v| map = v);
+    // A later value replaces an earlier one with the same key.
+    let expected: HashMap<_, _> =
+      vec![(1, "A".to_owned()), (2, "CC".to_owned())]
+        .into_iter()
+        .collect();
+    assert_eq!(map, expected);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).to_vec();
+    o.clone().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+    observable::from_iter(0..10)
+      .to_hash_map(|v| *v, |v| v * 2)
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
@@ -1,4 +1,8 @@
+use crate::observable::interval::SpawnHandle;
 use crate::prelude::*;
+use futures::prelude::*;
+use futures::task::{LocalSpawn, LocalSpawnExt};
+use futures_timer::Delay;
 use observable::observable_proxy_impl;
 use std::time::Duration;
 
@@ -34,18 +38,81 @@ where
   }
 }
 
+#[derive(Clone)]
+pub struct LocalDelayOp<S, Sp> {
+  pub(crate) source: S,
+  pub(crate) delay: Duration,
+  pub(crate) spawner: Sp,
+}
+
+observable_proxy_impl!(LocalDelayOp, S, Sp);
+
+impl<S, Sp> LocalObservable<'static> for LocalDelayOp<S, Sp>
+where
+  S: LocalObservable<'static> + 'static,
+  Sp: LocalSpawn,
+{
+  type Unsub = LocalSubscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + 'static>(
+    self,
+    subscriber: Subscriber<O, LocalSubscription>,
+  ) -> Self::Unsub {
+    let Self {
+      source,
+      delay,
+      mut spawner,
+    } = self;
+    let mut subscription = subscriber.subscription.clone();
+    let mut c_subscription = subscription.clone();
+    let f = Delay::new(delay).map(move |_| {
+      c_subscription.add(source.actual_subscribe(subscriber));
+    });
+    let handle = spawner
+      .spawn_local_with_handle(f)
+      .expect("spawn future for a delay failed");
+    subscription.add(SpawnHandle::new(handle));
+    subscription
+  }
+}
+
 #[test]
 fn smoke() {
-  use std::sync::{Arc, Mutex};
-  let value = Arc::new(Mutex::new(0));
+  let start = std::time::Instant::now();
+  let value = observable::of(1)
+    .delay(Duration::from_millis(50))
+    .blocking_last(None);
+  assert_eq!(value, Ok(1));
+  assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn local() {
+  use futures::executor::LocalPool;
+  use std::{cell::RefCell, rc::Rc, time::Instant};
+  let mut pool = LocalPool::new();
+  let value = Rc::new(RefCell::new(None));
   let c_value = value.clone();
+  let start = Instant::now();
   observable::of(1)
-    .delay(Duration::from_millis(50))
-    .to_shared()
-    .subscribe(move |v| {
-      *value.lock().unwrap() = v;
-    });
-  assert_eq!(*c_value.lock().unwrap(), 0);
-  std::thread::sleep(Duration::from_millis(60));
-  assert_eq!(*c_value.lock().unwrap(), 1);
+    .local_delay(Duration::from_millis(50), pool.spawner())
+    .subscribe(move |v| *c_value.borrow_mut() = Some(v));
+  assert_eq!(*value.borrow(), None);
+  pool.run();
+  assert_eq!(*value.borrow(), Some(1));
+  assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn local_unsubscribe() {
+  use futures::executor::LocalPool;
+  use std::{cell::Cell, rc::Rc};
+  let mut pool = LocalPool::new();
+  let emitted = Rc::new(Cell::new(false));
+  let c_emitted = emitted.clone();
+  observable::of(1)
+    .local_delay(Duration::from_millis(1), pool.spawner())
+    .subscribe(move |_| c_emitted.set(true))
+    .unsubscribe();
+  pool.run();
+  assert!(!emitted.get());
 }
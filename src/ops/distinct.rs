@@ -0,0 +1,234 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone)]
+pub struct DistinctOp<S> {
+  pub(crate) source: S,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: DistinctObserver {
+        observer: subscriber.observer,
+        seen: HashSet::new(),
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S> Observable for DistinctOp<S>
+where
+  S: Observable,
+  S::Item: Hash + Eq + Clone,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S> LocalObservable<'a> for DistinctOp<S>
+where
+  S: LocalObservable<'a>,
+  S::Item: Hash + Eq + Clone + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S> SharedObservable for DistinctOp<S>
+where
+  S: SharedObservable,
+  S::Item: Hash + Eq + Clone + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct DistinctObserver<O, Item> {
+  observer: O,
+  seen: HashSet<Item>,
+}
+
+impl<O, Item, Err> Observer<Item, Err> for DistinctObserver<O, Item>
+where
+  O: Observer<Item, Err>,
+  Item: Hash + Eq + Clone,
+{
+  fn next(&mut self, value: Item) {
+    if !self.seen.contains(&value) {
+      self.seen.insert(value.clone());
+      self.observer.next(value);
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[derive(Clone)]
+pub struct DistinctFlushOp<S, N> {
+  pub(crate) source: S,
+  pub(crate) flushes: N,
+}
+
+#[doc(hidden)]
+macro flush_observable_impl(
+  $subscription:ty, $sharer:path, $mutability_enabler:path,
+  $($marker:ident +)* $lf: lifetime)
+{
+  type Unsub = $subscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription;
+    let observer = $sharer($mutability_enabler(DistinctObserver {
+      observer: subscriber.observer,
+      seen: HashSet::new(),
+    }));
+    subscription.add(self.flushes.actual_subscribe(Subscriber {
+      observer: FlushObserver {
+        observer: observer.clone(),
+        subscription: subscription.clone(),
+        _p: PhantomData,
+      },
+      subscription: <$subscription>::default(),
+    }));
+    subscription.add(self.source.actual_subscribe(Subscriber {
+      observer,
+      subscription: subscription.clone(),
+    }));
+    subscription
+  }
+}
+
+impl<S, N> Observable for DistinctFlushOp<S, N>
+where
+  S: Observable,
+  S::Item: Hash + Eq + Clone,
+  N: Observable<Err = S::Err>,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, N> LocalObservable<'a> for DistinctFlushOp<S, N>
+where
+  S: LocalObservable<'a>,
+  S::Item: Hash + Eq + Clone + 'a,
+  N: LocalObservable<'a, Err = S::Err>,
+{
+  flush_observable_impl!(LocalSubscription, Rc::new, RefCell::new, 'a);
+}
+
+impl<S, N> SharedObservable for DistinctFlushOp<S, N>
+where
+  S: SharedObservable,
+  S::Item: Hash + Eq + Clone + Send + Sync + 'static,
+  S::Unsub: Send + Sync,
+  N: SharedObservable<Err = S::Err>,
+  N::Unsub: Send + Sync,
+{
+  flush_observable_impl!(
+    SharedSubscription,
+    Arc::new,
+    Mutex::new,
+    Send + Sync + 'static
+  );
+}
+
+trait Flush {
+  fn flush(&mut self);
+}
+
+impl<O, Item> Flush for Rc<RefCell<DistinctObserver<O, Item>>> {
+  fn flush(&mut self) { self.borrow_mut().seen.clear(); }
+}
+
+impl<O, Item> Flush for Arc<Mutex<DistinctObserver<O, Item>>> {
+  fn flush(&mut self) { self.lock().unwrap().seen.clear(); }
+}
+
+struct FlushObserver<O, U, Item> {
+  observer: O,
+  // Unsubscribes the source too, when the flush notifier errors.
+  subscription: U,
+  _p: PhantomData<Item>,
+}
+
+impl<O, U, Item, NotifierItem, Err> Observer<NotifierItem, Err>
+  for FlushObserver<O, U, Item>
+where
+  O: Flush + Observer<Item, Err>,
+  U: SubscriptionLike,
+{
+  fn next(&mut self, _: NotifierItem) { self.observer.flush(); }
+
+  fn error(&mut self, err: Err) {
+    self.observer.error(err);
+    self.subscription.unsubscribe();
+  }
+
+  fn complete(&mut self) {
+    // The source keeps being deduplicated, it just never flushes again.
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn smoke() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(vec![1, 2, 1, 3, 2, 4, 4])
+      .distinct()
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![1, 2, 3, 4]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn flush() {
+    let mut emitted = vec![];
+    {
+      let mut source = Subject::new();
+      let mut flushes = Subject::new();
+      source
+        .clone()
+        .distinct_with_flush(flushes.clone())
+        .subscribe(|v| emitted.push(v));
+
+      source.next(1);
+      source.next(1);
+      flushes.next(());
+      source.next(1);
+      source.next(2);
+      flushes.complete();
+      source.next(1);
+      source.next(2);
+    }
+    assert_eq!(emitted, vec![1, 1, 2]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).distinct();
+    o.clone().distinct().to_shared().subscribe(|_| {});
+    o.distinct_with_flush(observable::empty::<()>())
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
@@ -0,0 +1,215 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct DistinctUntilChangedOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) compare: F,
+}
+
+/// The type returned by
+/// [`distinct_until_changed`](Observable::distinct_until_changed).
+pub type DistinctUntilChangedEqOp<S, Item> =
+  DistinctUntilChangedOp<S, fn(&Item, &Item) -> bool>;
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: DistinctUntilChangedObserver {
+        observer: subscriber.observer,
+        compare: self.compare,
+        last: None,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, F> Observable for DistinctUntilChangedOp<S, F>
+where
+  S: Observable,
+  S::Item: Clone,
+  F: FnMut(&S::Item, &S::Item) -> bool,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for DistinctUntilChangedOp<S, F>
+where
+  S: LocalObservable<'a>,
+  S::Item: Clone + 'a,
+  F: FnMut(&S::Item, &S::Item) -> bool + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F> SharedObservable for DistinctUntilChangedOp<S, F>
+where
+  S: SharedObservable,
+  S::Item: Clone + Send + Sync + 'static,
+  F: FnMut(&S::Item, &S::Item) -> bool + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct DistinctUntilChangedObserver<O, F, Item> {
+  observer: O,
+  compare: F,
+  last: Option<Item>,
+}
+
+impl<O, F, Item, Err> Observer<Item, Err>
+  for DistinctUntilChangedObserver<O, F, Item>
+where
+  O: Observer<Item, Err>,
+  F: FnMut(&Item, &Item) -> bool,
+  Item: Clone,
+{
+  fn next(&mut self, value: Item) {
+    let changed = match &self.last {
+      Some(last) => !(self.compare)(last, &value),
+      None => true,
+    };
+    if changed {
+      self.last = Some(value.clone());
+      self.observer.next(value);
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[derive(Clone)]
+pub struct DistinctUntilKeyChangedOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) key: F,
+}
+
+#[doc(hidden)]
+macro key_observable_impl(
+  $subscription:ty, $($marker:ident +)* $lf: lifetime)
+{
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: DistinctUntilKeyChangedObserver {
+        observer: subscriber.observer,
+        key: self.key,
+        last: None,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, F, K> Observable for DistinctUntilKeyChangedOp<S, F>
+where
+  S: Observable,
+  F: FnMut(&S::Item) -> K,
+  K: PartialEq,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, F, K> LocalObservable<'a> for DistinctUntilKeyChangedOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: FnMut(&S::Item) -> K + 'a,
+  K: PartialEq + 'a,
+{
+  type Unsub = S::Unsub;
+  key_observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F, K> SharedObservable for DistinctUntilKeyChangedOp<S, F>
+where
+  S: SharedObservable,
+  F: FnMut(&S::Item) -> K + Send + Sync + 'static,
+  K: PartialEq + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  key_observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct DistinctUntilKeyChangedObserver<O, F, K> {
+  observer: O,
+  key: F,
+  last: Option<K>,
+}
+
+impl<O, F, K, Item, Err> Observer<Item, Err>
+  for DistinctUntilKeyChangedObserver<O, F, K>
+where
+  O: Observer<Item, Err>,
+  F: FnMut(&Item) -> K,
+  K: PartialEq,
+{
+  fn next(&mut self, value: Item) {
+    let key = (self.key)(&value);
+    if self.last.as_ref() != Some(&key) {
+      self.last = Some(key);
+      self.observer.next(value);
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn smoke() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(vec![1, 1, 2, 2, 2, 1, 3, 3])
+      .distinct_until_changed()
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![1, 2, 1, 3]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn by_compare() {
+    let mut emitted = vec![];
+    observable::from_iter(vec![1., 1.05, 1.5, 1.52, 1.])
+      .distinct_until_changed_by(|a: &f64, b: &f64| (a - b).abs() < 0.1)
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![1., 1.5, 1.]);
+  }
+
+  #[test]
+  fn by_key() {
+    let mut emitted = vec![];
+    observable::from_iter(vec![(1, 'a'), (1, 'b'), (2, 'c'), (1, 'd')])
+      .distinct_until_key_changed(|v| v.0)
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![(1, 'a'), (2, 'c'), (1, 'd')]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).distinct_until_changed();
+    o.clone()
+      .distinct_until_key_changed(|v| v / 2)
+      .to_shared()
+      .subscribe(|_| {});
+    o.distinct_until_changed_by(|a, b| a == b)
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
@@ -0,0 +1,115 @@
+use crate::observer::error_proxy_impl;
+use crate::prelude::*;
+
+/// An Observable that emits a sequence of values after its source completed.
+///
+/// This struct is created by the [`end_with`](Observable::end_with) method.
+#[derive(Clone)]
+pub struct EndWithOp<S, Item> {
+  pub(crate) source: S,
+  pub(crate) items: Vec<Item>,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: EndWithObserver {
+        observer: subscriber.observer,
+        items: self.items,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, Item> Observable for EndWithOp<S, Item>
+where
+  S: Observable<Item = Item>,
+{
+  type Item = Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, Item> LocalObservable<'a> for EndWithOp<S, Item>
+where
+  S: LocalObservable<'a, Item = Item>,
+  Item: 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, Item> SharedObservable for EndWithOp<S, Item>
+where
+  S: SharedObservable<Item = Item>,
+  Item: Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct EndWithObserver<O, Item> {
+  observer: O,
+  items: Vec<Item>,
+}
+
+impl<O, Item, Err> Observer<Item, Err> for EndWithObserver<O, Item>
+where
+  O: Observer<Item, Err>,
+{
+  #[inline]
+  fn next(&mut self, value: Item) { self.observer.next(value) }
+
+  error_proxy_impl!(Err, observer);
+
+  fn complete(&mut self) {
+    let observer = &mut self.observer;
+    self.items.drain(..).for_each(|v| observer.next(v));
+    self.observer.complete();
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn smoke() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(0..2)
+      .end_with(vec![8, 9])
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![0, 1, 8, 9]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn not_after_error() {
+    let mut emitted = vec![];
+    let mut error = false;
+    {
+      let mut subject = LocalSubject::new();
+      subject
+        .clone()
+        .end_with(vec![1])
+        .subscribe_err(|v| emitted.push(v), |_| error = true);
+      subject.next(0);
+      subject.error(());
+    }
+    assert_eq!(emitted, vec![0]);
+    assert!(error);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::of(1).end_with(vec![2]);
+    o.clone().end_with(vec![3]).to_shared().subscribe(|_| {});
+    o.start_with(vec![0]).to_shared().subscribe(|_| {});
+  }
+}
@@ -0,0 +1,143 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+
+/// An Observable that emits the index of the first value of its source that
+/// satisfies a predicate.
+///
+/// This struct is created by the [`find_index`](Observable::find_index)
+/// method.
+#[derive(Clone)]
+pub struct FindIndexOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) predicate: F,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: FindIndexObserver {
+        observer: subscriber.observer,
+        subscription: subscriber.subscription.clone(),
+        predicate: self.predicate,
+        index: 0,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, F> Observable for FindIndexOp<S, F>
+where
+  S: Observable,
+  F: FnMut(&S::Item) -> bool,
+{
+  type Item = usize;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for FindIndexOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: FnMut(&S::Item) -> bool + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F> SharedObservable for FindIndexOp<S, F>
+where
+  S: SharedObservable,
+  F: FnMut(&S::Item) -> bool + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct FindIndexObserver<O, U, F> {
+  observer: O,
+  subscription: U,
+  predicate: F,
+  index: usize,
+}
+
+impl<O, U, F, Item, Err> Observer<Item, Err> for FindIndexObserver<O, U, F>
+where
+  O: Observer<usize, Err>,
+  U: SubscriptionLike,
+  F: FnMut(&Item) -> bool,
+{
+  fn next(&mut self, value: Item) {
+    if (self.predicate)(&value) {
+      self.observer.next(self.index);
+      self.observer.complete();
+      self.subscription.unsubscribe();
+    } else {
+      self.index += 1;
+    }
+  }
+
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn find_index() {
+    let mut emitted = vec![];
+    let mut completed = 0;
+    observable::from_iter(vec!['a', 'b', 'c', 'b'])
+      .find_index(|v| *v == 'b')
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+    observable::from_iter(vec!['a', 'b', 'c'])
+      .find_index(|v| *v == 'd')
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+
+    assert_eq!(emitted, vec![1]);
+    assert_eq!(completed, 2);
+  }
+
+  #[test]
+  fn find() {
+    let mut emitted = vec![];
+    let mut visited = 0;
+    observable::from_iter(0..100)
+      .tap_next(|_| visited += 1)
+      .find(|v| v % 7 == 6)
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![6]);
+    assert_eq!(visited, 7);
+  }
+
+  #[test]
+  fn find_with_state() {
+    let mut emitted = vec![];
+    let mut sum = 0;
+    observable::from_iter(1..10)
+      .find(|v| {
+        sum += v;
+        sum > 10
+      })
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![5]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).find_index(|v| *v == 5);
+    o.clone().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+    observable::from_iter(0..10)
+      .find(|v| *v == 5)
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
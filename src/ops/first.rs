@@ -141,4 +141,55 @@ mod test {
     assert_eq!(default, 100);
     assert_eq!(default, 100);
   }
+
+  #[test]
+  fn first_where() {
+    let mut emitted = vec![];
+    let mut completed = 0;
+    observable::from_iter(0..10)
+      .first_where(|v| *v > 4)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+    observable::from_iter(0..10)
+      .first_where(|v| *v > 10)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+
+    assert_eq!(emitted, vec![5]);
+    assert_eq!(completed, 2);
+  }
+
+  #[test]
+  fn element_at() {
+    let mut emitted = vec![];
+    let mut visited = 0;
+    observable::from_iter(0..10)
+      .tap_next(|_| visited += 1)
+      .element_at(3)
+      .subscribe(|v| emitted.push(v));
+    observable::from_iter(0..3)
+      .element_at(3)
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![3]);
+    assert_eq!(visited, 4);
+  }
+
+  #[test]
+  fn element_at_or() {
+    let mut emitted = vec![];
+    observable::from_iter(0..10)
+      .element_at_or(3, 100)
+      .subscribe(|v| emitted.push(v));
+    observable::from_iter(0..3)
+      .element_at_or(3, 100)
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![3, 100]);
+  }
+
+  #[test]
+  fn element_at_fork_and_shared() {
+    let o = observable::from_iter(0..10).element_at(2);
+    o.clone().element_at_or(0, 1).to_shared().subscribe(|_| {});
+    o.first_where(|v| *v > 1).to_shared().subscribe(|_| {});
+  }
 }
@@ -189,4 +189,33 @@ mod test {
       .to_shared()
       .subscribe(|_| {});
   }
+
+  #[test]
+  fn last_where() {
+    let mut emitted = vec![];
+    let mut completed = 0;
+    observable::from_iter(0..10)
+      .last_where(|v| *v < 4)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+    observable::from_iter(0..10)
+      .last_where(|v| *v > 10)
+      .subscribe_complete(|v| emitted.push(v), || completed += 1);
+
+    assert_eq!(emitted, vec![3]);
+    assert_eq!(completed, 2);
+  }
+
+  #[test]
+  fn last_where_with_state() {
+    let mut emitted = vec![];
+    let mut sum = 0;
+    observable::from_iter(1..10)
+      .last_where(|v| {
+        sum += v;
+        sum < 20
+      })
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![5]);
+  }
 }
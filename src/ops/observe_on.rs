@@ -1,7 +1,9 @@
 use crate::prelude::*;
-use crate::scheduler::Scheduler;
+use crate::scheduler::{LocalSchedulerLike, Scheduler};
 use observable::observable_proxy_impl;
+use std::cell::RefCell;
 use std::marker::PhantomData;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
 #[derive(Clone)]
@@ -20,25 +22,27 @@ where
   S::Err: Clone + Send + Sync + 'static,
   SD: Scheduler + 'static,
 {
-  type Unsub = S::Unsub;
+  type Unsub = LocalSubscription;
   fn actual_subscribe<
     O: Observer<Self::Item, Self::Err> + Sync + Send + 'static,
   >(
     self,
     subscriber: Subscriber<O, SharedSubscription>,
   ) -> Self::Unsub {
+    let mut subscription = LocalSubscription::default();
+    subscription.add(subscriber.subscription.clone());
     let observer = ObserveOnObserver {
-      observer: Arc::new(Mutex::new(subscriber.observer)),
       proxy: subscriber.subscription.clone(),
+      observer: Arc::new(Mutex::new(subscriber)),
       scheduler: self.scheduler,
     };
-
-    let mut subscription = LocalSubscription::default();
-    subscription.add(subscriber.subscription);
-    self.source.actual_subscribe(Subscriber {
+    // The source gets a subscription of its own, so its completion doesn't
+    // cancel the notifications which are still scheduled.
+    subscription.add(self.source.actual_subscribe(Subscriber {
       observer,
-      subscription,
-    })
+      subscription: LocalSubscription::default(),
+    }));
+    subscription
   }
 }
 
@@ -51,25 +55,55 @@ where
   S: SharedObservable,
   S::Item: Clone + Send + 'static,
   S::Err: Clone + Send + 'static,
+  S::Unsub: Send + Sync,
   SD: Scheduler + Send + Sync + 'static,
 {
-  type Unsub = S::Unsub;
+  type Unsub = SharedSubscription;
   fn actual_subscribe<
     O: Observer<Self::Item, Self::Err> + Sync + Send + 'static,
   >(
     self,
     subscriber: Subscriber<O, SharedSubscription>,
   ) -> Self::Unsub {
-    let subscription = subscriber.subscription;
+    let mut subscription = subscriber.subscription.clone();
     let observer = ObserveOnObserver {
-      observer: Arc::new(Mutex::new(subscriber.observer)),
       proxy: subscription.clone(),
+      observer: Arc::new(Mutex::new(subscriber)),
       scheduler: self.scheduler,
     };
-    self.source.actual_subscribe(Subscriber {
+    subscription.add(self.source.actual_subscribe(Subscriber {
       observer,
-      subscription,
-    })
+      subscription: SharedSubscription::default(),
+    }));
+    subscription
+  }
+}
+
+impl<S, SD> LocalObservable<'static> for ObserveOnOp<'static, S, SD>
+where
+  S: LocalObservable<'static>,
+  S::Item: 'static,
+  S::Err: 'static,
+  SD: LocalSchedulerLike + 'static,
+{
+  type Unsub = LocalSubscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + 'static>(
+    self,
+    subscriber: Subscriber<O, LocalSubscription>,
+  ) -> Self::Unsub {
+    // The source gets a subscription of its own, so its completion doesn't
+    // cancel the notifications which are still scheduled.
+    let mut subscription = subscriber.subscription.clone();
+    let observer = LocalObserveOnObserver {
+      proxy: subscription.clone(),
+      subscriber: Rc::new(RefCell::new(subscriber)),
+      scheduler: self.scheduler,
+    };
+    subscription.add(self.source.actual_subscribe(Subscriber {
+      observer,
+      subscription: LocalSubscription::default(),
+    }));
+    subscription
   }
 }
 
@@ -79,71 +113,109 @@ pub struct ObserveOnObserver<O, SD, U> {
   scheduler: SD,
 }
 
-#[doc(hidden)]
-macro impl_observer($item: ident, $err: ident) {
-  fn next(&mut self, value: $item) {
+impl<O, SD> ObserveOnObserver<O, SD, SharedSubscription>
+where
+  O: Send + 'static,
+  SD: Scheduler,
+{
+  fn schedule<T: Send + 'static>(
+    &mut self,
+    state: T,
+    notify: impl FnOnce(&mut O, T) + Send + 'static,
+  ) {
+    let observer = self.observer.clone();
+    let mut proxy = self.proxy.clone();
     let s = self.scheduler.schedule(
-      |subscription, state| {
+      move |mut subscription, state| {
         if !subscription.is_closed() {
-          let (v, observer) = state;
-          observer.lock().unwrap().next(v);
+          notify(&mut observer.lock().unwrap(), state);
         }
+        // Closed before it's removed, so it's removed below if the task ran
+        // before it was added.
+        subscription.unsubscribe();
+        proxy.remove(&subscription);
       },
       None,
-      (value, self.observer.clone()),
+      state,
     );
-    self.proxy.add(s);
+    self.proxy.add(s.clone());
+    if s.is_closed() {
+      self.proxy.remove(&s);
+    }
   }
+}
 
-  fn error(&mut self, err: $err) {
-    let s = self.scheduler.schedule(
-      |mut subscription, state| {
-        if !subscription.is_closed() {
-          let (e, observer) = state;
-          observer.lock().unwrap().error(e);
-          subscription.unsubscribe();
-        }
-      },
-      None,
-      (err, self.observer.clone()),
-    );
-    self.proxy.add(s);
+impl<Item, Err, O, SD> Observer<Item, Err>
+  for ObserveOnObserver<O, SD, SharedSubscription>
+where
+  Item: Clone + Send + 'static,
+  Err: Clone + Send + 'static,
+  O: Observer<Item, Err> + Send + 'static,
+  SD: Scheduler,
+{
+  fn next(&mut self, value: Item) {
+    self.schedule(value, |observer, v| observer.next(v))
+  }
+
+  fn error(&mut self, err: Err) {
+    self.schedule(err, |observer, e| observer.error(e))
   }
 
   fn complete(&mut self) {
+    self.schedule((), |observer, _| observer.complete())
+  }
+}
+
+pub struct LocalObserveOnObserver<O, SD> {
+  subscriber: Rc<RefCell<Subscriber<O, LocalSubscription>>>,
+  // Kept apart from `subscriber`, which is borrowed while a notification is
+  // delivered, so a downstream observer may feed back into the source.
+  proxy: LocalSubscription,
+  scheduler: SD,
+}
+
+impl<O, SD> LocalObserveOnObserver<O, SD>
+where
+  O: 'static,
+  SD: LocalSchedulerLike,
+{
+  fn schedule<T: 'static>(
+    &mut self,
+    state: T,
+    notify: impl FnOnce(&mut Subscriber<O, LocalSubscription>, T) + 'static,
+  ) {
+    let subscriber = self.subscriber.clone();
+    let mut proxy = self.proxy.clone();
     let s = self.scheduler.schedule(
-      |mut subscription, observer| {
-        if !subscription.is_closed() {
-          observer.lock().unwrap().complete();
-          subscription.unsubscribe();
-        }
+      move |s, state| {
+        proxy.remove(&s);
+        notify(&mut subscriber.borrow_mut(), state);
       },
       None,
-      self.observer.clone(),
+      state,
     );
     self.proxy.add(s);
   }
 }
-impl<Item, Err, O, SD> Observer<Item, Err>
-  for ObserveOnObserver<O, SD, SharedSubscription>
-where
-  Item: Clone + Send + 'static,
-  Err: Clone + Send + 'static,
-  O: Observer<Item, Err> + Send + 'static,
-  SD: Scheduler,
-{
-  impl_observer!(Item, Err);
-}
 
-impl<Item, Err, O, SD> Observer<Item, Err>
-  for ObserveOnObserver<O, SD, LocalSubscription>
+impl<Item, Err, O, SD> Observer<Item, Err> for LocalObserveOnObserver<O, SD>
 where
-  Item: Clone + Send + 'static,
-  Err: Clone + Send + 'static,
-  O: Observer<Item, Err> + Send + 'static,
-  SD: Scheduler,
+  Item: 'static,
+  Err: 'static,
+  O: Observer<Item, Err> + 'static,
+  SD: LocalSchedulerLike,
 {
-  impl_observer!(Item, Err);
+  fn next(&mut self, value: Item) {
+    self.schedule(value, |subscriber, v| subscriber.next(v))
+  }
+
+  fn error(&mut self, err: Err) {
+    self.schedule(err, |subscriber, e| subscriber.error(e))
+  }
+
+  fn complete(&mut self) {
+    self.schedule((), |subscriber, _| subscriber.complete())
+  }
 }
 
 #[cfg(test)]
@@ -189,6 +261,33 @@ mod test {
   #[test]
   fn new_thread_unsubscribe() { unsubscribe_scheduler(Schedulers::NewThread) }
 
+  #[test]
+  fn pool_deliver_after_source_completed() {
+    deliver_after_source_completed(Schedulers::ThreadPool)
+  }
+
+  #[test]
+  fn new_thread_deliver_after_source_completed() {
+    deliver_after_source_completed(Schedulers::NewThread)
+  }
+
+  // The source completes before any scheduled notification runs, which must
+  // not cancel them.
+  fn deliver_after_source_completed(scheduler: Schedulers) {
+    let timeout = Some(Duration::from_secs(1));
+    let last = observable::of(1)
+      .observe_on(scheduler)
+      .blocking_last(timeout);
+    assert_eq!(last, Ok(1));
+
+    let values = observable::from_iter(0..3)
+      .to_shared()
+      .observe_on(scheduler)
+      .blocking_iter()
+      .collect::<Vec<_>>();
+    assert_eq!(values, vec![Ok(0), Ok(1), Ok(2)]);
+  }
+
   // #[test]
   // fn sync_unsubscribe() { unsubscribe_scheduler(Schedulers::Sync) }
 
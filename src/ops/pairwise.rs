@@ -0,0 +1,104 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+
+/// An Observable that emits the previous and the current value of its source
+/// as a pair.
+///
+/// This struct is created by the [`pairwise`](Observable::pairwise) method.
+#[derive(Clone)]
+pub struct PairwiseOp<S> {
+  pub(crate) source: S,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: PairwiseObserver {
+        observer: subscriber.observer,
+        prev: None,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S> Observable for PairwiseOp<S>
+where
+  S: Observable,
+  S::Item: Clone,
+{
+  type Item = (S::Item, S::Item);
+  type Err = S::Err;
+}
+
+impl<'a, S> LocalObservable<'a> for PairwiseOp<S>
+where
+  S: LocalObservable<'a>,
+  S::Item: Clone + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S> SharedObservable for PairwiseOp<S>
+where
+  S: SharedObservable,
+  S::Item: Clone + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct PairwiseObserver<O, Item> {
+  observer: O,
+  prev: Option<Item>,
+}
+
+impl<O, Item, Err> Observer<Item, Err> for PairwiseObserver<O, Item>
+where
+  O: Observer<(Item, Item), Err>,
+  Item: Clone,
+{
+  fn next(&mut self, value: Item) {
+    if let Some(prev) = self.prev.replace(value.clone()) {
+      self.observer.next((prev, value));
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn smoke() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(vec![1, 3, 6, 10])
+      .pairwise()
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![(1, 3), (3, 6), (6, 10)]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn single_value() {
+    let mut next_count = 0;
+    observable::of(1).pairwise().subscribe(|_| next_count += 1);
+    assert_eq!(next_count, 0);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).pairwise();
+    o.clone().pairwise().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+  }
+}
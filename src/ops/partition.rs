@@ -0,0 +1,147 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+use ops::ref_count::RefCount;
+
+/// One half of a [`partition`](Observable::partition): emits the values of
+/// its source for which `predicate` returns `matching`.
+#[derive(Clone)]
+pub struct PartitionOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) predicate: F,
+  pub(crate) matching: bool,
+}
+
+/// The type of both halves returned by
+/// [`partition`](Observable::partition).
+pub type Partition<S, Subject, Inner, F> =
+  PartitionOp<RefCount<Inner, ConnectableObservable<S, Subject>>, F>;
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: PartitionObserver {
+        observer: subscriber.observer,
+        predicate: self.predicate,
+        matching: self.matching,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, F> Observable for PartitionOp<S, F>
+where
+  S: Observable,
+  F: Fn(&S::Item) -> bool,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for PartitionOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: Fn(&S::Item) -> bool + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F> SharedObservable for PartitionOp<S, F>
+where
+  S: SharedObservable,
+  F: Fn(&S::Item) -> bool + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct PartitionObserver<O, F> {
+  observer: O,
+  predicate: F,
+  matching: bool,
+}
+
+impl<O, F, Item, Err> Observer<Item, Err> for PartitionObserver<O, F>
+where
+  O: Observer<Item, Err>,
+  F: Fn(&Item) -> bool,
+{
+  fn next(&mut self, value: Item) {
+    if (self.predicate)(&value) == self.matching {
+      self.observer.next(value);
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::cell::{Cell, RefCell};
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn smoke() {
+    let even = RefCell::new(vec![]);
+    let odd = RefCell::new(vec![]);
+    let completed = Cell::new(0);
+    let subscribed = Cell::new(0);
+    let mut subject = LocalSubject::new();
+    let (evens, odds) = subject
+      .clone()
+      .on_subscribe(|| subscribed.set(subscribed.get() + 1))
+      .partition(|v| v % 2 == 0);
+    evens.subscribe_complete(
+      |v| even.borrow_mut().push(v),
+      || completed.set(completed.get() + 1),
+    );
+    odds.subscribe_complete(
+      |v| odd.borrow_mut().push(v),
+      || completed.set(completed.get() + 1),
+    );
+    (0..6).for_each(|v| subject.next(v));
+    subject.complete();
+
+    assert_eq!(*even.borrow(), vec![0, 2, 4]);
+    assert_eq!(*odd.borrow(), vec![1, 3, 5]);
+    assert_eq!(completed.get(), 2);
+    assert_eq!(subscribed.get(), 1);
+  }
+
+  #[test]
+  fn unsubscribe_source_when_both_unsubscribed() {
+    let mut subject = LocalSubject::new();
+    let (evens, odds) = subject.clone().partition(|v: &i32| v % 2 == 0);
+    let mut s1 = evens.subscribe(|_| {});
+    let mut s2 = odds.subscribe(|_| {});
+    subject.next(1);
+    assert_eq!(subject.subscribed_size(), 1);
+    s1.unsubscribe();
+    subject.next(2);
+    assert_eq!(subject.subscribed_size(), 1);
+    s2.unsubscribe();
+    subject.next(3);
+    assert_eq!(subject.subscribed_size(), 0);
+  }
+
+  #[test]
+  fn shared() {
+    let even = Arc::new(Mutex::new(vec![]));
+    let c_even = even.clone();
+    let mut subject = SharedSubject::new();
+    let (evens, odds) = subject.clone().partition(|v: &i32| v % 2 == 0);
+    evens
+      .to_shared()
+      .subscribe(move |v| c_even.lock().unwrap().push(v));
+    odds.to_shared().subscribe(|_| {});
+    (0..4).for_each(|v| subject.next(v));
+
+    assert_eq!(*even.lock().unwrap(), vec![0, 2]);
+  }
+}
@@ -0,0 +1,356 @@
+//! Reductions by a comparison or a key, like `min_by` and `count_by`.
+//!
+//! The result is accumulated in place by the observer of a [`ReduceByOp`],
+//! and emitted once the source completes, so neither the items nor the
+//! accumulated result are ever cloned.
+use crate::observer::error_proxy_impl;
+use crate::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Orders two items.
+pub trait Compare<Item> {
+  fn compare(&mut self, a: &Item, b: &Item) -> Ordering;
+}
+
+impl<Item, F> Compare<Item> for F
+where
+  F: FnMut(&Item, &Item) -> Ordering,
+{
+  #[inline]
+  fn compare(&mut self, a: &Item, b: &Item) -> Ordering { self(a, b) }
+}
+
+/// Orders items by the key the wrapped function extracts from them.
+#[derive(Clone)]
+pub struct ByKey<F>(pub(crate) F);
+
+impl<Item, K, F> Compare<Item> for ByKey<F>
+where
+  K: Ord,
+  F: FnMut(&Item) -> K,
+{
+  #[inline]
+  fn compare(&mut self, a: &Item, b: &Item) -> Ordering {
+    (self.0)(a).cmp(&(self.0)(b))
+  }
+}
+
+/// Accumulates the items of an Observable into a result, which a
+/// [`ReduceByOp`] emits once the source completes.
+pub trait Accumulator<Item> {
+  type Output;
+
+  fn accumulate(&mut self, value: Item);
+
+  /// Takes the result, if there is one.
+  fn finish(&mut self) -> Option<Self::Output>;
+}
+
+/// An Observable that accumulates the items of its source and emits the
+/// result once the source completes.
+///
+/// This struct is created by methods like
+/// [`min_by`](Observable::min_by) and [`count_by`](Observable::count_by).
+#[derive(Clone)]
+pub struct ReduceByOp<S, A> {
+  pub(crate) source: S,
+  pub(crate) accumulator: A,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: ReduceByObserver {
+        observer: subscriber.observer,
+        accumulator: self.accumulator,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, A> Observable for ReduceByOp<S, A>
+where
+  S: Observable,
+  A: Accumulator<S::Item>,
+{
+  type Item = A::Output;
+  type Err = S::Err;
+}
+
+impl<'a, S, A> LocalObservable<'a> for ReduceByOp<S, A>
+where
+  S: LocalObservable<'a>,
+  A: Accumulator<S::Item> + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, A> SharedObservable for ReduceByOp<S, A>
+where
+  S: SharedObservable,
+  A: Accumulator<S::Item> + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct ReduceByObserver<O, A> {
+  observer: O,
+  accumulator: A,
+}
+
+impl<Item, Err, O, A> Observer<Item, Err> for ReduceByObserver<O, A>
+where
+  O: Observer<A::Output, Err>,
+  A: Accumulator<Item>,
+{
+  #[inline]
+  fn next(&mut self, value: Item) { self.accumulator.accumulate(value) }
+
+  fn complete(&mut self) {
+    if let Some(result) = self.accumulator.finish() {
+      self.observer.next(result);
+    }
+    self.observer.complete()
+  }
+
+  error_proxy_impl!(Err, observer);
+}
+
+/// Keeps the least or the greatest item seen so far. Like [`Iterator::min_by`]
+/// and [`Iterator::max_by`], the first of several least items and the last of
+/// several greatest items wins.
+pub struct MinMaxBy<C, Item> {
+  compare: C,
+  max: bool,
+  acc: Option<Item>,
+}
+
+impl<C, Item> MinMaxBy<C, Item> {
+  pub(crate) fn new(compare: C, max: bool) -> Self {
+    MinMaxBy {
+      compare,
+      max,
+      acc: None,
+    }
+  }
+}
+
+// Only the function is cloned, an operator is cloned before any item is
+// accumulated.
+impl<C: Clone, Item> Clone for MinMaxBy<C, Item> {
+  fn clone(&self) -> Self { MinMaxBy::new(self.compare.clone(), self.max) }
+}
+
+impl<C, Item> Accumulator<Item> for MinMaxBy<C, Item>
+where
+  C: Compare<Item>,
+{
+  type Output = Item;
+
+  fn accumulate(&mut self, value: Item) {
+    let replace = match &self.acc {
+      Some(acc) if self.max => {
+        self.compare.compare(acc, &value) != Ordering::Greater
+      }
+      Some(acc) => self.compare.compare(&value, acc) == Ordering::Less,
+      None => true,
+    };
+    if replace {
+      self.acc = Some(value);
+    }
+  }
+
+  #[inline]
+  fn finish(&mut self) -> Option<Item> { self.acc.take() }
+}
+
+/// Counts the items per key.
+pub struct CountBy<F, K> {
+  key: F,
+  counts: HashMap<K, usize>,
+}
+
+impl<F, K> CountBy<F, K> {
+  pub(crate) fn new(key: F) -> Self {
+    CountBy {
+      key,
+      counts: HashMap::new(),
+    }
+  }
+}
+
+impl<F: Clone, K> Clone for CountBy<F, K> {
+  fn clone(&self) -> Self { CountBy::new(self.key.clone()) }
+}
+
+impl<Item, F, K> Accumulator<Item> for CountBy<F, K>
+where
+  K: Hash + Eq,
+  F: FnMut(&Item) -> K,
+{
+  type Output = HashMap<K, usize>;
+
+  fn accumulate(&mut self, value: Item) {
+    *self.counts.entry((self.key)(&value)).or_insert(0) += 1;
+  }
+
+  #[inline]
+  fn finish(&mut self) -> Option<Self::Output> {
+    Some(std::mem::take(&mut self.counts))
+  }
+}
+
+/// Groups the items by key, in the order they were emitted.
+pub struct ToMapBy<F, K, Item> {
+  key: F,
+  groups: HashMap<K, Vec<Item>>,
+}
+
+impl<F, K, Item> ToMapBy<F, K, Item> {
+  pub(crate) fn new(key: F) -> Self {
+    ToMapBy {
+      key,
+      groups: HashMap::new(),
+    }
+  }
+}
+
+impl<F: Clone, K, Item> Clone for ToMapBy<F, K, Item> {
+  fn clone(&self) -> Self { ToMapBy::new(self.key.clone()) }
+}
+
+impl<Item, F, K> Accumulator<Item> for ToMapBy<F, K, Item>
+where
+  K: Hash + Eq,
+  F: FnMut(&Item) -> K,
+{
+  type Output = HashMap<K, Vec<Item>>;
+
+  fn accumulate(&mut self, value: Item) {
+    let key = (self.key)(&value);
+    self.groups.entry(key).or_default().push(value);
+  }
+
+  #[inline]
+  fn finish(&mut self) -> Option<Self::Output> {
+    Some(std::mem::take(&mut self.groups))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::collections::HashMap;
+
+  #[test]
+  fn min_max_by() {
+    let words = vec!["bb", "a", "ccc", "dd", "e"];
+    let mut min = None;
+    let mut max = None;
+    observable::from_iter(words.clone())
+      .min_by(|a, b| a.len().cmp(&b.len()))
+      .subscribe(|v| min = Some(v));
+    observable::from_iter(words)
+      .max_by(|a, b| a.len().cmp(&b.len()))
+      .subscribe(|v| max = Some(v));
+    assert_eq!(min, Some("a"));
+    assert_eq!(max, Some("ccc"));
+  }
+
+  #[test]
+  fn min_max_by_key_ties() {
+    let pairs = vec![(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+    let mut min = None;
+    let mut max = None;
+    observable::from_iter(pairs.clone())
+      .min_by_key(|p| p.0)
+      .subscribe(|v| min = Some(v));
+    observable::from_iter(pairs)
+      .max_by_key(|p| p.0)
+      .subscribe(|v| max = Some(v));
+    // Same as `Iterator::min_by_key` and `Iterator::max_by_key`.
+    assert_eq!(min, Some((0, 'b')));
+    assert_eq!(max, Some((1, 'c')));
+  }
+
+  #[test]
+  fn min_by_key_empty() {
+    let mut emitted = 0;
+    let mut completed = false;
+    observable::empty().min_by_key(|v: &i32| *v).subscribe_all(
+      |_| emitted += 1,
+      |_: ()| {},
+      || completed = true,
+    );
+    assert_eq!(emitted, 0);
+    assert!(completed);
+  }
+
+  #[test]
+  fn count_by() {
+    let mut counts = HashMap::new();
+    observable::from_iter(0..10)
+      .count_by(|v| v % 3)
+      .subscribe(|v| counts = v);
+    let expected: HashMap<_, _> =
+      vec![(0, 4), (1, 3), (2, 3)].into_iter().collect();
+    assert_eq!(counts, expected);
+  }
+
+  #[test]
+  fn count_by_empty() {
+    let mut counts = None;
+    observable::empty()
+      .count_by(|v: &i32| *v)
+      .subscribe(|v| counts = Some(v));
+    assert_eq!(counts, Some(HashMap::new()));
+  }
+
+  #[test]
+  fn to_map_by() {
+    let mut groups = HashMap::new();
+    observable::from_iter(vec!["apple", "avocado", "banana", "apricot"])
+      .to_map_by(|s| s.chars().next().unwrap())
+      .subscribe(|v| groups = v);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&'a'], vec!["apple", "avocado", "apricot"]);
+    assert_eq!(groups[&'b'], vec!["banana"]);
+  }
+
+  #[test]
+  fn items_and_keys_need_no_clone() {
+    #[derive(Debug, PartialEq, Eq, Hash)]
+    struct NoClone(i32);
+
+    let mut max = None;
+    let mut groups = HashMap::new();
+    observable::from_iter(vec![NoClone(2), NoClone(3), NoClone(1)])
+      .max_by_key(|v| v.0)
+      .subscribe(|v| max = Some(v));
+    observable::from_iter(vec![NoClone(2), NoClone(3), NoClone(4)])
+      .to_map_by(|v| NoClone(v.0 % 2))
+      .subscribe(|v| groups = v);
+    assert_eq!(max, Some(NoClone(3)));
+    assert_eq!(groups[&NoClone(0)], vec![NoClone(2), NoClone(4)]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).max_by_key(|v| *v % 4);
+    o.clone().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+    observable::from_iter(0..10)
+      .to_map_by(|v| v % 2)
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
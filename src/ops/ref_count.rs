@@ -131,13 +131,14 @@ where
     subscriber: Subscriber<O, LocalSubscription>,
   ) -> Self::Unsub {
     let mut inner = (self.0).0.borrow_mut();
-    inner.connectable.clone().actual_subscribe(subscriber);
+    let subscription = inner.connectable.clone().actual_subscribe(subscriber);
     if inner.connection.is_none() {
       inner.connection = Some(inner.connectable.clone().connect());
     }
     let connection = inner.connection.as_ref().unwrap().clone();
     RefCountSubscription {
-      subscription: inner.connectable.subject.subscription.clone(),
+      subscription,
+      subject_subscription: inner.connectable.subject.subscription.clone(),
       connection,
     }
   }
@@ -166,13 +167,14 @@ where
     subscriber: Subscriber<O, SharedSubscription>,
   ) -> Self::Unsub {
     let mut inner = (self.0).0.lock().unwrap();
-    inner.connectable.clone().actual_subscribe(subscriber);
+    let subscription = inner.connectable.clone().actual_subscribe(subscriber);
     if inner.connection.is_none() {
       inner.connection = Some(inner.connectable.clone().connect());
     }
     let connection = inner.connection.as_ref().unwrap().clone();
     RefCountSubscription {
-      subscription: inner.connectable.subject.subscription.clone(),
+      subscription,
+      subject_subscription: inner.connectable.subject.subscription.clone(),
       connection,
     }
   }
@@ -181,28 +183,36 @@ where
 #[derive(Clone)]
 pub struct RefCountSubscription<S, C> {
   subscription: S,
+  // Holds the subscriptions of all subscribers still connected.
+  subject_subscription: S,
   connection: C,
 }
 
-impl<S, C> SubscriptionLike for RefCountSubscription<S, C>
-where
-  S: TearDownSize,
-  C: SubscriptionLike,
-{
-  fn unsubscribe(&mut self) {
-    self.subscription.unsubscribe();
-    if self.subscription.teardown_size() == 0 {
-      self.connection.unsubscribe();
+#[doc(hidden)]
+macro ref_count_subscription_impl($subscription:ty) {
+  impl<C> SubscriptionLike for RefCountSubscription<$subscription, C>
+  where
+    C: SubscriptionLike,
+  {
+    fn unsubscribe(&mut self) {
+      self.subscription.unsubscribe();
+      self.subject_subscription.remove(&self.subscription);
+      if self.subject_subscription.teardown_size() == 0 {
+        self.connection.unsubscribe();
+      }
     }
-  }
 
-  #[inline(always)]
-  fn is_closed(&self) -> bool { self.subscription.is_closed() }
+    #[inline(always)]
+    fn is_closed(&self) -> bool { self.subscription.is_closed() }
 
-  #[inline(always)]
-  fn inner_addr(&self) -> *const () { self.subscription.inner_addr() }
+    #[inline(always)]
+    fn inner_addr(&self) -> *const () { self.subscription.inner_addr() }
+  }
 }
 
+ref_count_subscription_impl!(LocalSubscription);
+ref_count_subscription_impl!(SharedSubscription);
+
 #[test]
 fn smoke() {
   let mut accept1 = 0;
@@ -236,6 +246,28 @@ fn auto_unsubscribe() {
   assert_eq!(accept2, 1);
 }
 
+#[test]
+fn keep_connected_while_subscribed() {
+  let mut accept1 = 0;
+  let mut accept2 = 0;
+  {
+    let mut subject = Subject::new();
+    let ref_count = subject.clone().publish().ref_count();
+    let mut s1 = ref_count.clone().subscribe(|v| accept1 = v);
+    let mut s2 = ref_count.clone().subscribe(|v| accept2 = v);
+    subject.next(1);
+    s1.unsubscribe();
+    subject.next(2);
+    assert_eq!(subject.subscribed_size(), 1);
+    s2.unsubscribe();
+    subject.next(3);
+    assert_eq!(subject.subscribed_size(), 0);
+  }
+
+  assert_eq!(accept1, 1);
+  assert_eq!(accept2, 2);
+}
+
 #[test]
 fn fork_and_shared() {
   observable::of(1).publish().ref_count().subscribe(|_| {});
@@ -0,0 +1,284 @@
+use crate::prelude::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// An Observable that emits whether two Observables emit the same sequence of
+/// values.
+///
+/// This struct is created by the
+/// [`sequence_equal`](Observable::sequence_equal) method.
+#[derive(Clone)]
+pub struct SequenceEqualOp<A, B> {
+  pub(crate) a: A,
+  pub(crate) b: B,
+}
+
+#[doc(hidden)]
+macro observable_impl(
+  $subscription:ty, $sharer:path, $mutability_enabler:path,
+  $($marker:ident +)* $lf: lifetime)
+{
+  type Unsub = $subscription;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription;
+    let observer = $sharer($mutability_enabler(SequenceEqualObserver {
+      observer: subscriber.observer,
+      subscription: subscription.clone(),
+      a: SequenceState::default(),
+      b: SequenceState::default(),
+    }));
+    // Added upfront, so a decided comparison also stops a source that's still
+    // emitting synchronously.
+    let a_subscription = <$subscription>::default();
+    subscription.add(a_subscription.clone());
+    subscription.add(self.a.actual_subscribe(Subscriber {
+      observer: SideObserver(observer.clone(), Side::A, PhantomData),
+      subscription: a_subscription,
+    }));
+    let b_subscription = <$subscription>::default();
+    subscription.add(b_subscription.clone());
+    subscription.add(self.b.actual_subscribe(Subscriber {
+      observer: SideObserver(observer, Side::B, PhantomData),
+      subscription: b_subscription,
+    }));
+    subscription
+  }
+}
+
+impl<A, B> Observable for SequenceEqualOp<A, B>
+where
+  A: Observable,
+  A::Item: PartialEq,
+  B: Observable<Item = A::Item, Err = A::Err>,
+{
+  type Item = bool;
+  type Err = A::Err;
+}
+
+impl<'a, A, B> LocalObservable<'a> for SequenceEqualOp<A, B>
+where
+  A: LocalObservable<'a>,
+  A::Item: PartialEq + 'a,
+  B: LocalObservable<'a, Item = A::Item, Err = A::Err>,
+{
+  observable_impl!(LocalSubscription, Rc::new, RefCell::new, 'a);
+}
+
+impl<A, B> SharedObservable for SequenceEqualOp<A, B>
+where
+  A: SharedObservable,
+  A::Item: PartialEq + Send + Sync + 'static,
+  A::Unsub: Send + Sync,
+  B: SharedObservable<Item = A::Item, Err = A::Err>,
+  B::Unsub: Send + Sync,
+{
+  observable_impl!(
+    SharedSubscription,
+    Arc::new,
+    Mutex::new,
+    Send + Sync + 'static
+  );
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+  A,
+  B,
+}
+
+enum SequenceEvent<Item> {
+  Next(Side, Item),
+  Complete(Side),
+}
+
+struct SequenceState<Item> {
+  // Values this side emitted that the other side didn't catch up with yet.
+  pending: VecDeque<Item>,
+  completed: bool,
+}
+
+impl<Item> Default for SequenceState<Item> {
+  fn default() -> Self {
+    SequenceState {
+      pending: VecDeque::default(),
+      completed: false,
+    }
+  }
+}
+
+struct SequenceEqualObserver<O, U, Item> {
+  observer: O,
+  subscription: U,
+  a: SequenceState<Item>,
+  b: SequenceState<Item>,
+}
+
+impl<O, U, Item> SequenceEqualObserver<O, U, Item> {
+  fn sides(
+    &mut self,
+    side: Side,
+  ) -> (&mut SequenceState<Item>, &mut SequenceState<Item>) {
+    match side {
+      Side::A => (&mut self.a, &mut self.b),
+      Side::B => (&mut self.b, &mut self.a),
+    }
+  }
+}
+
+impl<O, U, Item, Err> Observer<SequenceEvent<Item>, Err>
+  for SequenceEqualObserver<O, U, Item>
+where
+  O: Observer<bool, Err>,
+  U: SubscriptionLike,
+  Item: PartialEq,
+{
+  fn next(&mut self, event: SequenceEvent<Item>) {
+    let equal = match event {
+      SequenceEvent::Next(side, value) => {
+        let (this, other) = self.sides(side);
+        if let Some(expected) = other.pending.pop_front() {
+          if expected != value { Some(false) } else { None }
+        } else if other.completed {
+          Some(false)
+        } else {
+          this.pending.push_back(value);
+          None
+        }
+      }
+      SequenceEvent::Complete(side) => {
+        let (this, other) = self.sides(side);
+        if !other.pending.is_empty() {
+          Some(false)
+        } else if other.completed {
+          Some(true)
+        } else {
+          this.completed = true;
+          None
+        }
+      }
+    };
+    if let Some(equal) = equal {
+      self.observer.next(equal);
+      self.observer.complete();
+      self.subscription.unsubscribe();
+    }
+  }
+
+  fn error(&mut self, err: Err) {
+    self.observer.error(err);
+    self.subscription.unsubscribe();
+  }
+
+  fn complete(&mut self) {}
+}
+
+struct SideObserver<O, Item>(O, Side, PhantomData<Item>);
+
+impl<O, Item, Err> Observer<Item, Err> for SideObserver<O, Item>
+where
+  O: Observer<SequenceEvent<Item>, Err>,
+{
+  fn next(&mut self, value: Item) {
+    self.0.next(SequenceEvent::Next(self.1, value));
+  }
+
+  fn error(&mut self, err: Err) { self.0.error(err); }
+
+  fn complete(&mut self) { self.0.next(SequenceEvent::Complete(self.1)); }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::cell::RefCell;
+
+  fn sequence_equal(a: Vec<i32>, b: Vec<i32>) -> bool {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(a)
+      .sequence_equal(observable::from_iter(b))
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+    assert!(completed);
+    assert_eq!(emitted.len(), 1);
+    emitted[0]
+  }
+
+  #[test]
+  fn equal() {
+    assert!(sequence_equal(vec![1, 2, 3], vec![1, 2, 3]));
+    assert!(sequence_equal(vec![], vec![]));
+  }
+
+  #[test]
+  fn not_equal() {
+    assert!(!sequence_equal(vec![1, 2, 3], vec![1, 5, 3]));
+    assert!(!sequence_equal(vec![1, 2], vec![1, 2, 3]));
+    assert!(!sequence_equal(vec![1, 2, 3], vec![1, 2]));
+    assert!(!sequence_equal(vec![], vec![1]));
+  }
+
+  #[test]
+  fn interleaved() {
+    let emitted = RefCell::new(vec![]);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    a.clone()
+      .sequence_equal(b.clone())
+      .subscribe(|v| emitted.borrow_mut().push(v));
+    b.next(1);
+    a.next(1);
+    a.next(2);
+    b.next(2);
+    a.complete();
+    assert!(emitted.borrow().is_empty());
+    b.complete();
+    assert_eq!(*emitted.borrow(), vec![true]);
+  }
+
+  #[test]
+  fn unsubscribe_both_on_mismatch() {
+    let emitted = RefCell::new(vec![]);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    a.clone()
+      .sequence_equal(b.clone())
+      .subscribe(|v| emitted.borrow_mut().push(v));
+    a.next(1);
+    b.next(2);
+    a.next(3);
+    b.next(3);
+    assert_eq!(a.subscribed_size(), 0);
+    assert_eq!(b.subscribed_size(), 0);
+    assert_eq!(*emitted.borrow(), vec![false]);
+  }
+
+  #[test]
+  fn error() {
+    let error = RefCell::new(None);
+    let mut a = LocalSubject::new();
+    let mut b = LocalSubject::new();
+    a.clone()
+      .sequence_equal(b.clone())
+      .subscribe_err(|_| {}, |e| *error.borrow_mut() = Some(e));
+    a.next(1);
+    b.error("oops");
+    assert_eq!(*error.borrow(), Some("oops"));
+    assert_eq!(a.subscribed_size(), 1);
+    a.next(2);
+    assert_eq!(a.subscribed_size(), 0);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o =
+      observable::from_iter(0..10).sequence_equal(observable::from_iter(0..10));
+    o.clone().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+  }
+}
@@ -0,0 +1,193 @@
+use crate::prelude::*;
+use std::error::Error;
+use std::fmt;
+
+/// The error emitted by [`single`](Observable::single) and
+/// [`single_or_default`](Observable::single_or_default).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SingleError<Err> {
+  /// The source completed without emitting any value.
+  Empty,
+  /// The source emitted more than one value.
+  TooMany,
+  /// The source emitted an error itself.
+  Source(Err),
+}
+
+impl<Err: fmt::Display> fmt::Display for SingleError<Err> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SingleError::Empty => write!(f, "observable completed without a value"),
+      SingleError::TooMany => {
+        write!(f, "observable emitted more than one value")
+      }
+      SingleError::Source(err) => err.fmt(f),
+    }
+  }
+}
+
+impl<Err: Error + 'static> Error for SingleError<Err> {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      SingleError::Source(err) => Some(err),
+      _ => None,
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct SingleOp<S, Item> {
+  pub(crate) source: S,
+  pub(crate) default: Option<Item>,
+}
+
+impl<Item, S> Observable for SingleOp<S, Item>
+where
+  S: Observable<Item = Item>,
+{
+  type Item = Item;
+  type Err = SingleError<S::Err>;
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let subscriber = Subscriber {
+      observer: SingleObserver {
+        observer: subscriber.observer,
+        subscription: subscriber.subscription.clone(),
+        default: self.default,
+        value: None,
+      },
+      subscription: subscriber.subscription,
+    };
+    self.source.actual_subscribe(subscriber)
+  }
+}
+
+impl<'a, Item, S> LocalObservable<'a> for SingleOp<S, Item>
+where
+  S: LocalObservable<'a, Item = Item>,
+  Item: 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<Item, S> SharedObservable for SingleOp<S, Item>
+where
+  S: SharedObservable<Item = Item>,
+  Item: Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct SingleObserver<O, U, Item> {
+  observer: O,
+  subscription: U,
+  default: Option<Item>,
+  value: Option<Item>,
+}
+
+impl<O, U, Item, Err> Observer<Item, Err> for SingleObserver<O, U, Item>
+where
+  O: Observer<Item, SingleError<Err>>,
+  U: SubscriptionLike,
+{
+  fn next(&mut self, value: Item) {
+    if self.value.is_none() {
+      self.value = Some(value);
+    } else {
+      self.observer.error(SingleError::TooMany);
+      self.subscription.unsubscribe();
+    }
+  }
+
+  fn error(&mut self, err: Err) {
+    self.observer.error(SingleError::Source(err));
+  }
+
+  fn complete(&mut self) {
+    match self.value.take().or_else(|| self.default.take()) {
+      Some(v) => {
+        self.observer.next(v);
+        self.observer.complete();
+      }
+      None => self.observer.error(SingleError::Empty),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::SingleError;
+  use crate::prelude::*;
+
+  #[test]
+  fn single() {
+    let mut value = None;
+    let mut completed = false;
+    observable::of(1).single().subscribe_all(
+      |v| value = Some(v),
+      |_| {},
+      || completed = true,
+    );
+
+    assert_eq!(value, Some(1));
+    assert!(completed);
+  }
+
+  #[test]
+  fn single_too_many() {
+    let mut error = None;
+    let mut visited = 0;
+    observable::from_iter(0..10)
+      .tap_next(|_| visited += 1)
+      .single()
+      .subscribe_err(|_| panic!("no value expected"), |e| error = Some(e));
+
+    assert_eq!(error, Some(SingleError::TooMany));
+    assert_eq!(visited, 2);
+  }
+
+  #[test]
+  fn single_empty() {
+    let mut error = None;
+    observable::empty::<i32>()
+      .single()
+      .subscribe_err(|_| {}, |e| error = Some(e));
+    assert_eq!(error, Some(SingleError::Empty));
+
+    error = None;
+    observable::throw(())
+      .single()
+      .subscribe_err(|_: ()| {}, |e| error = Some(e));
+    assert_eq!(error, Some(SingleError::Source(())));
+  }
+
+  #[test]
+  fn single_or_default() {
+    let mut emitted = vec![];
+    observable::empty()
+      .single_or_default(5)
+      .subscribe_err(|v| emitted.push(v), |_| {});
+    observable::of(1)
+      .single_or_default(5)
+      .subscribe_err(|v| emitted.push(v), |_| {});
+
+    assert_eq!(emitted, vec![5, 1]);
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::of(1).single();
+    o.clone().to_shared().subscribe_err(|_| {}, |_| {});
+    o.single_or_default(2)
+      .to_shared()
+      .subscribe_err(|_| {}, |_| {});
+  }
+}
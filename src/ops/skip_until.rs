@@ -0,0 +1,213 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct SkipUntilOp<S, N> {
+  pub(crate) source: S,
+  pub(crate) notifier: N,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $sharer:path, $mutability_enabler:path,
+                      $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription;
+    // We need to keep a reference to the observer from two places
+    let shared_observer = $sharer($mutability_enabler(SkipUntilObserver {
+      observer: subscriber.observer,
+      open: false,
+    }));
+    let notifier_subscription = <$subscription>::default();
+    let notifier_subscriber = Subscriber {
+      observer: SkipUntilNotifierObserver {
+        main_observer: shared_observer.clone(),
+        main_subscription: subscription.clone(),
+        subscription: notifier_subscription.clone(),
+        _p: PhantomData,
+      },
+      subscription: notifier_subscription,
+    };
+    subscription.add(self.notifier.actual_subscribe(notifier_subscriber));
+    subscription.add(self.source.actual_subscribe(Subscriber {
+      observer: shared_observer,
+      subscription: subscription.clone(),
+    }));
+    subscription
+  }
+}
+
+observable_proxy_impl!(SkipUntilOp, S, N);
+
+impl<'a, S, N> LocalObservable<'a> for SkipUntilOp<S, N>
+where
+  S: LocalObservable<'a> + 'a,
+  N: LocalObservable<'a, Err = S::Err> + 'a,
+{
+  type Unsub = LocalSubscription;
+  observable_impl!(LocalSubscription, Rc::new, RefCell::new, 'a);
+}
+
+impl<S, N> SharedObservable for SkipUntilOp<S, N>
+where
+  S: SharedObservable,
+  N: SharedObservable<Err = S::Err>,
+  S::Item: Send + Sync + 'static,
+  S::Unsub: Send + Sync,
+  N::Unsub: Send + Sync,
+{
+  type Unsub = SharedSubscription;
+  observable_impl!(
+    SharedSubscription,
+    Arc::new,
+    Mutex::new,
+    Send + Sync + 'static
+  );
+}
+
+pub struct SkipUntilObserver<O> {
+  observer: O,
+  open: bool,
+}
+
+impl<O, Item, Err> Observer<Item, Err> for SkipUntilObserver<O>
+where
+  O: Observer<Item, Err>,
+{
+  fn next(&mut self, value: Item) {
+    if self.open {
+      self.observer.next(value);
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+trait OpenGate {
+  fn open_gate(&mut self);
+}
+
+impl<O> OpenGate for Rc<RefCell<SkipUntilObserver<O>>> {
+  fn open_gate(&mut self) { self.borrow_mut().open = true; }
+}
+
+impl<O> OpenGate for Arc<Mutex<SkipUntilObserver<O>>> {
+  fn open_gate(&mut self) { self.lock().unwrap().open = true; }
+}
+
+pub struct SkipUntilNotifierObserver<O, U, Item> {
+  // We need access to main observer in order to let values pass as soon as
+  // notifier fired
+  main_observer: O,
+  // We need to unsubscribe everything if notifier errors
+  main_subscription: U,
+  // The notifier is of no use anymore once the gate is open
+  subscription: U,
+  _p: PhantomData<Item>,
+}
+
+impl<O, U, NotifierItem, Item, Err> Observer<NotifierItem, Err>
+  for SkipUntilNotifierObserver<O, U, Item>
+where
+  O: OpenGate + Observer<Item, Err>,
+  U: SubscriptionLike,
+{
+  fn next(&mut self, _: NotifierItem) {
+    self.main_observer.open_gate();
+    self.subscription.unsubscribe();
+  }
+
+  fn error(&mut self, err: Err) {
+    self.main_observer.error(err);
+    self.main_subscription.unsubscribe();
+  }
+
+  fn complete(&mut self) {
+    // Do nothing
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::{Arc, Mutex};
+
+  use crate::prelude::*;
+
+  #[test]
+  fn base_function() {
+    let mut emitted = vec![];
+    let mut completed_count = 0;
+    {
+      let mut notifier = Subject::new();
+      let mut source = Subject::new();
+      source
+        .clone()
+        .skip_until(notifier.clone())
+        .subscribe_complete(|i| emitted.push(i), || completed_count += 1);
+      source.next(5);
+      notifier.next(());
+      source.next(6);
+      source.next(7);
+      source.complete();
+    }
+    assert_eq!(emitted, vec![6, 7]);
+    assert_eq!(completed_count, 1);
+  }
+
+  #[test]
+  fn unsubscribe_notifier_when_open() {
+    let mut notifier = LocalSubject::new();
+    let mut source = LocalSubject::new();
+    source
+      .clone()
+      .skip_until(notifier.clone())
+      .subscribe(|_: i32| {});
+    assert_eq!(notifier.subscribed_size(), 1);
+    notifier.next(());
+    notifier.next(());
+    assert_eq!(notifier.subscribed_size(), 0);
+    source.next(1);
+  }
+
+  #[test]
+  fn notifier_error() {
+    let mut emitted = vec![];
+    let mut error = None;
+    {
+      let mut notifier: LocalSubject<(), _> = Subject::new();
+      let mut source = Subject::new();
+      source
+        .clone()
+        .skip_until(notifier.clone())
+        .subscribe_err(|v| emitted.push(v), |e| error = Some(e));
+      notifier.error("oops");
+      source.next(1);
+    }
+    assert!(emitted.is_empty());
+    assert_eq!(error, Some("oops"));
+  }
+
+  #[test]
+  fn into_shared() {
+    let emitted = Arc::new(Mutex::new(vec![]));
+    let c_emitted = emitted.clone();
+    let mut notifier = Subject::new();
+    let mut source = Subject::new();
+    source
+      .clone()
+      .skip_until(notifier.clone())
+      .to_shared()
+      .subscribe(move |i| c_emitted.lock().unwrap().push(i));
+    source.next(5);
+    notifier.next(());
+    source.next(6);
+    assert_eq!(*emitted.lock().unwrap(), vec![6]);
+  }
+}
@@ -0,0 +1,120 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct SkipWhileOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) callback: F,
+}
+
+#[doc(hidden)]
+macro observable_impl(
+  $subscription:ty, $source:ident, $($marker:ident +)* $lf: lifetime)
+{
+  type Unsub = $source::Unsub;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let subscriber = Subscriber {
+      observer: SkipWhileObserver {
+        observer: subscriber.observer,
+        callback: self.callback,
+        skipping: true,
+      },
+      subscription: subscriber.subscription,
+    };
+    self.source.actual_subscribe(subscriber)
+  }
+}
+
+impl<S, F> Observable for SkipWhileOp<S, F>
+where
+  S: Observable,
+  F: FnMut(&S::Item) -> bool,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for SkipWhileOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: FnMut(&S::Item) -> bool + 'a,
+{
+  observable_impl!(LocalSubscription, S, 'a);
+}
+
+impl<S, F> SharedObservable for SkipWhileOp<S, F>
+where
+  S: SharedObservable,
+  F: FnMut(&S::Item) -> bool + Send + Sync + 'static,
+{
+  observable_impl!(SharedSubscription, S, Send + Sync + 'static);
+}
+
+pub struct SkipWhileObserver<O, F> {
+  observer: O,
+  callback: F,
+  skipping: bool,
+}
+
+impl<O, Item, Err, F> Observer<Item, Err> for SkipWhileObserver<O, F>
+where
+  O: Observer<Item, Err>,
+  F: FnMut(&Item) -> bool,
+{
+  fn next(&mut self, value: Item) {
+    if self.skipping && !(self.callback)(&value) {
+      self.skipping = false;
+    }
+    if !self.skipping {
+      self.observer.next(value);
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn base_function() {
+    let mut completed = false;
+    let mut emitted = vec![];
+
+    observable::from_iter(vec![1, 2, 5, 1, 6, 2])
+      .skip_while(|v| v < &5)
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![5, 1, 6, 2]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn skip_while_support_fork() {
+    let mut nc1 = 0;
+    let mut nc2 = 0;
+    {
+      let skip_while5 = observable::from_iter(0..100).skip_while(|v| v < &5);
+      let f1 = skip_while5.clone();
+      let f2 = skip_while5;
+
+      f1.skip_while(|v| v < &10).subscribe(|_| nc1 += 1);
+      f2.skip_while(|v| v < &10).subscribe(|_| nc2 += 1);
+    }
+    assert_eq!(nc1, 90);
+    assert_eq!(nc2, 90);
+  }
+
+  #[test]
+  fn into_shared() {
+    observable::from_iter(0..100)
+      .skip_while(|v| v < &5)
+      .skip_while(|v| v < &5)
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
@@ -0,0 +1,128 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+use std::collections::VecDeque;
+
+/// An Observable that emits the last `size` values of its source every time
+/// it emits.
+///
+/// This struct is created by the [`sliding`](Observable::sliding) method.
+#[derive(Clone)]
+pub struct SlidingOp<S> {
+  pub(crate) source: S,
+  pub(crate) size: usize,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: SlidingObserver {
+        observer: subscriber.observer,
+        window: VecDeque::with_capacity(self.size),
+        size: self.size,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S> Observable for SlidingOp<S>
+where
+  S: Observable,
+  S::Item: Clone,
+{
+  type Item = Vec<S::Item>;
+  type Err = S::Err;
+}
+
+impl<'a, S> LocalObservable<'a> for SlidingOp<S>
+where
+  S: LocalObservable<'a>,
+  S::Item: Clone + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S> SharedObservable for SlidingOp<S>
+where
+  S: SharedObservable,
+  S::Item: Clone + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct SlidingObserver<O, Item> {
+  observer: O,
+  window: VecDeque<Item>,
+  size: usize,
+}
+
+impl<O, Item, Err> Observer<Item, Err> for SlidingObserver<O, Item>
+where
+  O: Observer<Vec<Item>, Err>,
+  Item: Clone,
+{
+  fn next(&mut self, value: Item) {
+    if self.window.len() == self.size {
+      self.window.pop_front();
+    }
+    self.window.push_back(value);
+    if self.window.len() == self.size {
+      self.observer.next(self.window.iter().cloned().collect());
+    }
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+
+  #[test]
+  fn smoke() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(0..5)
+      .sliding(3)
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![vec![0, 1, 2], vec![1, 2, 3], vec![2, 3, 4]]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn fewer_values_than_size() {
+    let mut next_count = 0;
+    observable::from_iter(0..2)
+      .sliding(3)
+      .subscribe(|_| next_count += 1);
+    assert_eq!(next_count, 0);
+  }
+
+  #[test]
+  fn moving_average() {
+    let mut emitted = vec![];
+    observable::from_iter(vec![1., 2., 3., 4.])
+      .sliding(2)
+      .map(|w| w.iter().sum::<f64>() / w.len() as f64)
+      .subscribe(|v| emitted.push(v));
+    assert_eq!(emitted, vec![1.5, 2.5, 3.5]);
+  }
+
+  #[test]
+  #[should_panic]
+  fn zero_size() { observable::of(1).sliding(0); }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).sliding(2);
+    o.clone().sliding(2).to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+  }
+}
@@ -0,0 +1,128 @@
+use crate::prelude::*;
+
+/// An Observable that emits a sequence of values before mirroring its source.
+///
+/// This struct is created by the [`start_with`](Observable::start_with)
+/// method.
+#[derive(Clone)]
+pub struct StartWithOp<S, Item> {
+  pub(crate) source: S,
+  pub(crate) items: Vec<Item>,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    mut subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription.clone();
+    // Emitted before subscribing, so a hot source can't slip in between.
+    for v in self.items {
+      if subscription.is_closed() {
+        return subscription;
+      }
+      subscriber.next(v);
+    }
+    // The prefix may already have been enough for the downstream, then the
+    // source isn't started at all.
+    if !subscription.is_closed() {
+      subscription.add(self.source.actual_subscribe(subscriber));
+    }
+    subscription
+  }
+}
+
+impl<S, Item> Observable for StartWithOp<S, Item>
+where
+  S: Observable<Item = Item>,
+{
+  type Item = Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, Item> LocalObservable<'a> for StartWithOp<S, Item>
+where
+  S: LocalObservable<'a, Item = Item>,
+  Item: 'a,
+{
+  type Unsub = LocalSubscription;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, Item> SharedObservable for StartWithOp<S, Item>
+where
+  S: SharedObservable<Item = Item>,
+  Item: Send + Sync + 'static,
+  S::Unsub: Send + Sync,
+{
+  type Unsub = SharedSubscription;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::cell::Cell;
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn smoke() {
+    let mut emitted = vec![];
+    let mut completed = false;
+    observable::from_iter(3..5)
+      .start_with(vec![0, 1, 2])
+      .subscribe_complete(|v| emitted.push(v), || completed = true);
+
+    assert_eq!(emitted, vec![0, 1, 2, 3, 4]);
+    assert!(completed);
+  }
+
+  #[test]
+  fn prefix_before_hot_source() {
+    let emitted = Arc::new(Mutex::new(vec![]));
+    let c_emitted = emitted.clone();
+    let mut subject = SharedSubject::new();
+    subject
+      .clone()
+      .start_with(vec![0])
+      .to_shared()
+      .subscribe(move |v| c_emitted.lock().unwrap().push(v));
+    assert_eq!(*emitted.lock().unwrap(), vec![0]);
+    subject.next(1);
+    assert_eq!(*emitted.lock().unwrap(), vec![0, 1]);
+  }
+
+  #[test]
+  fn stop_when_unsubscribed_during_prefix() {
+    let mut emitted = vec![];
+    observable::from_iter(3..5)
+      .start_with(vec![0, 1, 2])
+      .take(2)
+      .subscribe(|v| emitted.push(v));
+    assert_eq!(emitted, vec![0, 1]);
+  }
+
+  #[test]
+  fn skip_source_when_closed_by_prefix() {
+    let subscribed = Cell::new(false);
+    let mut emitted = vec![];
+    observable::create(|mut s| {
+      subscribed.set(true);
+      s.next(9);
+      s.complete();
+    })
+    .start_with(vec![1, 2])
+    .take(2)
+    .subscribe(|v| emitted.push(v));
+    assert_eq!(emitted, vec![1, 2]);
+    assert!(!subscribed.get());
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::of(1).start_with(vec![0]);
+    o.clone().start_with(vec![-1]).to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+  }
+}
@@ -0,0 +1,576 @@
+//! Running and windowed statistics over numeric Observables.
+//!
+//! Every operator here emits the statistic over the current window each time
+//! the source emits, as an `f64`. Append [`last`](Observable::last) to get
+//! the statistic over the whole stream only once it completes.
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// A number statistics can be computed over.
+pub trait Numeric {
+  fn to_f64(&self) -> f64;
+}
+
+#[doc(hidden)]
+macro numeric_impl($($t:ty),*) {
+  $(
+    impl Numeric for $t {
+      #[inline]
+      fn to_f64(&self) -> f64 { *self as f64 }
+    }
+  )*
+}
+
+numeric_impl!(
+  i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+/// The values a statistic is computed over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Window {
+  /// All values emitted so far.
+  All,
+  /// The last `n` values.
+  Count(usize),
+  /// The values emitted within the given duration, including the current
+  /// one.
+  Time(Duration),
+}
+
+/// An aggregate which can be updated incrementally as values enter and leave
+/// a [`Window`].
+pub trait Statistic {
+  /// Adds a value entering the window.
+  fn insert(&mut self, value: f64);
+
+  /// Removes a value leaving the window. It's never called with
+  /// [`Window::All`].
+  fn remove(&mut self, value: f64);
+
+  /// The statistic over the values currently in the window.
+  fn value(&self) -> f64;
+
+  /// Called with the window of the operator on subscription, before any
+  /// value is inserted, for statistics computed differently depending on
+  /// the window.
+  #[inline]
+  fn set_window(&mut self, _window: Window) {}
+}
+
+// Welford's algorithm, extended to removals.
+#[derive(Clone, Debug, Default)]
+struct Moments {
+  count: usize,
+  mean: f64,
+  m2: f64,
+}
+
+impl Moments {
+  fn insert(&mut self, value: f64) {
+    self.count += 1;
+    let delta = value - self.mean;
+    self.mean += delta / self.count as f64;
+    self.m2 += delta * (value - self.mean);
+  }
+
+  fn remove(&mut self, value: f64) {
+    self.count -= 1;
+    if self.count == 0 {
+      *self = Moments::default();
+    } else {
+      let delta = value - self.mean;
+      self.mean -= delta / self.count as f64;
+      self.m2 -= delta * (value - self.mean);
+    }
+  }
+
+  // Rounding errors of removals may push `m2` slightly below zero.
+  fn variance(&self) -> f64 {
+    if self.count == 0 {
+      0.
+    } else {
+      (self.m2 / self.count as f64).max(0.)
+    }
+  }
+}
+
+/// The arithmetic mean.
+#[derive(Clone, Debug, Default)]
+pub struct Mean(Moments);
+
+impl Statistic for Mean {
+  fn insert(&mut self, value: f64) { self.0.insert(value) }
+  fn remove(&mut self, value: f64) { self.0.remove(value) }
+  fn value(&self) -> f64 { self.0.mean }
+}
+
+/// The population variance.
+#[derive(Clone, Debug, Default)]
+pub struct Variance(Moments);
+
+impl Statistic for Variance {
+  fn insert(&mut self, value: f64) { self.0.insert(value) }
+  fn remove(&mut self, value: f64) { self.0.remove(value) }
+  fn value(&self) -> f64 { self.0.variance() }
+}
+
+/// The population standard deviation.
+#[derive(Clone, Debug, Default)]
+pub struct StdDev(Moments);
+
+impl Statistic for StdDev {
+  fn insert(&mut self, value: f64) { self.0.insert(value) }
+  fn remove(&mut self, value: f64) { self.0.remove(value) }
+  fn value(&self) -> f64 { self.0.variance().sqrt() }
+}
+
+/// A percentile, linearly interpolated between the closest ranks.
+///
+/// Over a bounded window the percentile is exact. Over [`Window::All`] it's
+/// estimated with the P² algorithm, which needs constant memory no matter
+/// how many values are emitted. Which one applies follows from the window of
+/// the operator it's used with.
+#[derive(Clone, Debug)]
+pub struct Percentile {
+  fraction: f64,
+  estimator: Option<P2>,
+  // All values of a bounded window, or the first values of an unbounded one
+  // until the estimator is initialized.
+  sorted: Vec<f64>,
+}
+
+impl Percentile {
+  /// Creates the `percentile` (between 0 and 100) statistic.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `percentile` isn't between 0 and 100.
+  pub fn new(percentile: f64) -> Self {
+    assert!(
+      (0. ..=100.).contains(&percentile),
+      "percentile must be between 0 and 100"
+    );
+    Percentile {
+      fraction: percentile / 100.,
+      estimator: None,
+      sorted: vec![],
+    }
+  }
+}
+
+impl Statistic for Percentile {
+  fn insert(&mut self, value: f64) {
+    match &mut self.estimator {
+      Some(p2) if p2.is_initialized() => p2.insert(value),
+      Some(p2) => {
+        insert_sorted(&mut self.sorted, value);
+        if self.sorted.len() == P2_MARKERS {
+          p2.init(&self.sorted);
+          self.sorted.clear();
+        }
+      }
+      None => insert_sorted(&mut self.sorted, value),
+    }
+  }
+
+  fn remove(&mut self, value: f64) {
+    if let Ok(idx) = self.sorted.binary_search_by(|v| compare(*v, value)) {
+      self.sorted.remove(idx);
+    }
+  }
+
+  fn value(&self) -> f64 {
+    match &self.estimator {
+      Some(p2) if p2.is_initialized() => p2.value(),
+      _ => interpolate(&self.sorted, self.fraction),
+    }
+  }
+
+  fn set_window(&mut self, window: Window) {
+    self.estimator = match window {
+      Window::All => Some(P2::new(self.fraction)),
+      _ => None,
+    };
+  }
+}
+
+fn compare(a: f64, b: f64) -> Ordering {
+  a.partial_cmp(&b).unwrap_or(Ordering::Less)
+}
+
+fn insert_sorted(sorted: &mut Vec<f64>, value: f64) {
+  let idx = match sorted.binary_search_by(|v| compare(*v, value)) {
+    Ok(idx) | Err(idx) => idx,
+  };
+  sorted.insert(idx, value);
+}
+
+fn interpolate(sorted: &[f64], fraction: f64) -> f64 {
+  if sorted.is_empty() {
+    return 0.;
+  }
+  let rank = fraction * (sorted.len() - 1) as f64;
+  let lower = rank.floor() as usize;
+  let upper = rank.ceil() as usize;
+  sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+const P2_MARKERS: usize = 5;
+
+/// The P² algorithm of Jain and Chlamtac for estimating a quantile without
+/// storing the values.
+#[derive(Clone, Debug)]
+struct P2 {
+  initialized: bool,
+  heights: [f64; P2_MARKERS],
+  positions: [f64; P2_MARKERS],
+  desired: [f64; P2_MARKERS],
+  increments: [f64; P2_MARKERS],
+}
+
+impl P2 {
+  fn new(fraction: f64) -> Self {
+    P2 {
+      initialized: false,
+      heights: [0.; P2_MARKERS],
+      positions: [1., 2., 3., 4., 5.],
+      desired: [
+        1.,
+        1. + 2. * fraction,
+        1. + 4. * fraction,
+        3. + 2. * fraction,
+        5.,
+      ],
+      increments: [0., fraction / 2., fraction, (1. + fraction) / 2., 1.],
+    }
+  }
+
+  fn is_initialized(&self) -> bool { self.initialized }
+
+  fn init(&mut self, sorted: &[f64]) {
+    self.heights.copy_from_slice(sorted);
+    self.initialized = true;
+  }
+
+  fn insert(&mut self, value: f64) {
+    let h = &mut self.heights;
+    let cell = if value < h[0] {
+      h[0] = value;
+      0
+    } else if value >= h[4] {
+      h[4] = value;
+      3
+    } else {
+      (0..4).find(|&i| value < h[i + 1]).unwrap_or(3)
+    };
+    self.positions[cell + 1..].iter_mut().for_each(|n| *n += 1.);
+    self
+      .desired
+      .iter_mut()
+      .zip(self.increments.iter())
+      .for_each(|(d, inc)| *d += inc);
+
+    for i in 1..4 {
+      let n = &self.positions;
+      let d = self.desired[i] - n[i];
+      if (d >= 1. && n[i + 1] - n[i] > 1.)
+        || (d <= -1. && n[i - 1] - n[i] < -1.)
+      {
+        let d = d.signum();
+        let parabolic = self.parabolic(i, d);
+        self.heights[i] = if self.heights[i - 1] < parabolic
+          && parabolic < self.heights[i + 1]
+        {
+          parabolic
+        } else {
+          self.linear(i, d)
+        };
+        self.positions[i] += d;
+      }
+    }
+  }
+
+  fn parabolic(&self, i: usize, d: f64) -> f64 {
+    let (q, n) = (&self.heights, &self.positions);
+    q[i]
+      + d / (n[i + 1] - n[i - 1])
+        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+          + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+  }
+
+  fn linear(&self, i: usize, d: f64) -> f64 {
+    let (q, n) = (&self.heights, &self.positions);
+    let j = if d > 0. { i + 1 } else { i - 1 };
+    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+  }
+
+  fn value(&self) -> f64 { self.heights[2] }
+}
+
+/// An Observable that emits a [`Statistic`] over a [`Window`] of its
+/// source's values.
+///
+/// This struct is created by the
+/// [`statistic_with`](Observable::statistic_with) method and its shorthands
+/// like [`moving_average`](Observable::moving_average).
+#[derive(Clone)]
+pub struct StatisticOp<S, T, C> {
+  pub(crate) source: S,
+  pub(crate) statistic: T,
+  pub(crate) window: Window,
+  pub(crate) clock: C,
+}
+
+impl<S, T, C> StatisticOp<S, T, C> {
+  pub(crate) fn new(source: S, statistic: T, window: Window, clock: C) -> Self {
+    assert!(
+      window != Window::Count(0),
+      "count window size must be non-zero"
+    );
+    StatisticOp {
+      source,
+      statistic,
+      window,
+      clock,
+    }
+  }
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut statistic = self.statistic;
+    statistic.set_window(self.window);
+    self.source.actual_subscribe(Subscriber {
+      observer: StatisticObserver {
+        observer: subscriber.observer,
+        statistic,
+        window: self.window,
+        clock: self.clock,
+        values: VecDeque::new(),
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, T, C> Observable for StatisticOp<S, T, C>
+where
+  S: Observable,
+  S::Item: Numeric,
+  T: Statistic,
+  C: Clock,
+{
+  type Item = f64;
+  type Err = S::Err;
+}
+
+impl<'a, S, T, C> LocalObservable<'a> for StatisticOp<S, T, C>
+where
+  S: LocalObservable<'a>,
+  S::Item: Numeric,
+  T: Statistic + 'a,
+  C: Clock + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, T, C> SharedObservable for StatisticOp<S, T, C>
+where
+  S: SharedObservable,
+  S::Item: Numeric,
+  T: Statistic + Send + Sync + 'static,
+  C: Clock + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct StatisticObserver<O, T, C> {
+  observer: O,
+  statistic: T,
+  window: Window,
+  clock: C,
+  // The values currently in a bounded window, with their time of arrival.
+  values: VecDeque<(Instant, f64)>,
+}
+
+impl<O, T, C, Item, Err> Observer<Item, Err> for StatisticObserver<O, T, C>
+where
+  O: Observer<f64, Err>,
+  T: Statistic,
+  C: Clock,
+  Item: Numeric,
+{
+  fn next(&mut self, value: Item) {
+    let value = value.to_f64();
+    match self.window {
+      Window::All => {}
+      Window::Count(size) => {
+        if self.values.len() == size {
+          let (_, old) = self.values.pop_front().unwrap();
+          self.statistic.remove(old);
+        }
+        self.values.push_back((self.clock.now(), value));
+      }
+      Window::Time(dur) => {
+        let now = self.clock.now();
+        while let Some((at, old)) = self.values.front() {
+          if now.saturating_duration_since(*at) < dur {
+            break;
+          }
+          self.statistic.remove(*old);
+          self.values.pop_front();
+        }
+        self.values.push_back((now, value));
+      }
+    }
+    self.statistic.insert(value);
+    self.observer.next(self.statistic.value());
+  }
+
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use float_cmp::approx_eq;
+
+  fn collect<S>(o: S) -> Vec<f64>
+  where
+    S: LocalObservable<'static, Item = f64, Err = ()>,
+  {
+    let values = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+    let c_values = values.clone();
+    o.subscribe(move |v| c_values.borrow_mut().push(v));
+    values.take()
+  }
+
+  #[test]
+  fn average_of_ints() {
+    let avg = collect(
+      observable::from_iter(vec![3, 4, 5, 6, 7]).moving_average(Window::All),
+    );
+    assert_eq!(avg, vec![3., 3.5, 4., 4.5, 5.]);
+  }
+
+  #[test]
+  fn count_window_average() {
+    let avg = collect(
+      observable::from_iter(vec![1u8, 2, 3, 10, 20])
+        .moving_average(Window::Count(2)),
+    );
+    assert_eq!(avg, vec![1., 1.5, 2.5, 6.5, 15.]);
+  }
+
+  #[test]
+  fn time_window_average() {
+    let clock = VirtualClock::default();
+    let mut emitted = vec![];
+    {
+      let mut subject = LocalSubject::new();
+      subject
+        .clone()
+        .statistic_with(
+          Mean::default(),
+          Window::Time(Duration::from_secs(2)),
+          clock.clone(),
+        )
+        .subscribe(|v| emitted.push(v));
+      subject.next(1.);
+      clock.advance(Duration::from_secs(1));
+      subject.next(3.);
+      clock.advance(Duration::from_secs(1));
+      subject.next(8.);
+      clock.advance(Duration::from_secs(5));
+      subject.next(4.);
+    }
+    assert_eq!(emitted, vec![1., 2., 5.5, 4.]);
+  }
+
+  #[test]
+  fn variance_and_std_dev() {
+    let values = vec![2, 4, 4, 4, 5, 5, 7, 9];
+    let variance = collect(
+      observable::from_iter(values.clone()).moving_variance(Window::All),
+    );
+    assert!(approx_eq!(f64, *variance.last().unwrap(), 4.));
+    let std_dev =
+      collect(observable::from_iter(values).moving_std_dev(Window::Count(3)));
+    // Over [2, 4, 4], [4, 4, 4], ..., [5, 7, 9].
+    assert!(approx_eq!(
+      f64,
+      std_dev[2],
+      (8f64 / 9.).sqrt(),
+      epsilon = 1e-9
+    ));
+    assert!(approx_eq!(f64, std_dev[3], 0., epsilon = 1e-9));
+    assert!(approx_eq!(
+      f64,
+      std_dev[7],
+      (8f64 / 3.).sqrt(),
+      epsilon = 1e-9
+    ));
+  }
+
+  #[test]
+  fn exact_percentile() {
+    let median = collect(
+      observable::from_iter(vec![5, 1, 4, 2, 3])
+        .moving_percentile(Window::Count(4), 50.),
+    );
+    assert_eq!(median, vec![5., 3., 4., 3., 2.5]);
+  }
+
+  #[test]
+  fn estimated_percentile() {
+    // A deterministic permutation of 0..1000.
+    let values = (0..1000).map(|i| (i * 7919) % 1000);
+    let p90 = collect(
+      observable::from_iter(values).moving_percentile(Window::All, 90.),
+    );
+    assert!((p90.last().unwrap() - 900.).abs() < 20.);
+    // Exact until the estimator has enough values.
+    assert_eq!(p90[0], 0.);
+  }
+
+  #[test]
+  fn percentile_follows_operator_window() {
+    let median = collect(
+      observable::from_iter(vec![1, 9, 2, 8, 3, 7, 100, 100]).statistic_with(
+        Percentile::new(50.),
+        Window::Count(3),
+        SystemClock,
+      ),
+    );
+    assert_eq!(median, vec![1., 5., 2., 8., 3., 7., 7., 100.]);
+  }
+
+  #[test]
+  #[should_panic]
+  fn zero_count_window() { observable::of(1).moving_average(Window::Count(0)); }
+
+  #[test]
+  #[should_panic]
+  fn percentile_out_of_range() { Percentile::new(101.); }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).moving_average(Window::Count(3));
+    o.clone().to_shared().subscribe(|_| {});
+    o.to_shared().subscribe(|_| {});
+    observable::from_iter(0..10)
+      .moving_percentile(Window::All, 50.)
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
@@ -0,0 +1,377 @@
+use crate::prelude::*;
+
+/// An Observable that calls a side-effect observer with a reference to every
+/// notification before forwarding it downstream unchanged.
+///
+/// This struct is created by the [`tap`](Observable::tap) method, and the
+/// `tap_next`, `tap_error` and `tap_complete` shorthands.
+#[derive(Clone)]
+pub struct TapOp<S, T> {
+  pub(crate) source: S,
+  pub(crate) tap: T,
+}
+
+/// The type returned by [`tap_next`](Observable::tap_next).
+pub type TapNextOp<S, Err, F> = TapOp<S, ObserverAll<F, fn(&Err), fn()>>;
+
+/// The type returned by [`tap_error`](Observable::tap_error).
+pub type TapErrorOp<S, Item, F> = TapOp<S, ObserverAll<fn(&Item), F, fn()>>;
+
+/// The type returned by [`tap_complete`](Observable::tap_complete).
+pub type TapCompleteOp<S, Item, Err, F> =
+  TapOp<S, ObserverAll<fn(&Item), fn(&Err), F>>;
+
+#[doc(hidden)]
+macro tap_observable_impl(
+  $subscription:ty, $($marker:ident +)* $lf: lifetime)
+{
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: TapObserver {
+        observer: subscriber.observer,
+        tap: self.tap,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, T> Observable for TapOp<S, T>
+where
+  S: Observable,
+  T: for<'r> Observer<&'r S::Item, &'r S::Err>,
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, T> LocalObservable<'a> for TapOp<S, T>
+where
+  S: LocalObservable<'a>,
+  T: for<'r> Observer<&'r S::Item, &'r S::Err> + 'a,
+{
+  type Unsub = S::Unsub;
+  tap_observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, T> SharedObservable for TapOp<S, T>
+where
+  S: SharedObservable,
+  T: for<'r> Observer<&'r S::Item, &'r S::Err> + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  tap_observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct TapObserver<O, T> {
+  observer: O,
+  tap: T,
+}
+
+impl<Item, Err, O, T> Observer<Item, Err> for TapObserver<O, T>
+where
+  O: Observer<Item, Err>,
+  T: for<'r> Observer<&'r Item, &'r Err>,
+{
+  fn next(&mut self, value: Item) {
+    self.tap.next(&value);
+    self.observer.next(value);
+  }
+
+  fn error(&mut self, err: Err) {
+    self.tap.error(&err);
+    self.observer.error(err);
+  }
+
+  fn complete(&mut self) {
+    self.tap.complete();
+    self.observer.complete();
+  }
+}
+
+/// An Observable that calls a function every time it's subscribed to, right
+/// before subscribing to its source.
+///
+/// This struct is created by the
+/// [`on_subscribe`](Observable::on_subscribe) method.
+#[derive(Clone)]
+pub struct OnSubscribeOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) func: F,
+}
+
+#[doc(hidden)]
+macro on_subscribe_observable_impl(
+  $subscription:ty, $($marker:ident +)* $lf: lifetime)
+{
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    mut self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    (self.func)();
+    self.source.actual_subscribe(subscriber)
+  }
+}
+
+impl<S, F> Observable for OnSubscribeOp<S, F>
+where
+  S: Observable,
+  F: FnMut(),
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for OnSubscribeOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: FnMut(),
+{
+  type Unsub = S::Unsub;
+  on_subscribe_observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F> SharedObservable for OnSubscribeOp<S, F>
+where
+  S: SharedObservable,
+  F: FnMut(),
+{
+  type Unsub = S::Unsub;
+  on_subscribe_observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+/// An Observable that calls a function when its subscription is torn down by
+/// `unsubscribe` before the source terminated.
+///
+/// This struct is created by the
+/// [`on_unsubscribe`](Observable::on_unsubscribe) method.
+#[derive(Clone)]
+pub struct OnUnsubscribeOp<S, F> {
+  pub(crate) source: S,
+  pub(crate) func: F,
+}
+
+#[doc(hidden)]
+macro on_unsubscribe_observable_impl(
+  $subscription:ty, $($marker:ident +)* $lf: lifetime)
+{
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    let mut subscription = subscriber.subscription.clone();
+    let hook = <$subscription>::default();
+    subscription.add(self.source.actual_subscribe(Subscriber {
+      observer: OnUnsubscribeObserver {
+        observer: subscriber.observer,
+        hook: hook.clone(),
+      },
+      subscription: subscriber.subscription,
+    }));
+    subscription.add(UnsubscribeHook {
+      s: hook,
+      f: self.func,
+    });
+    subscription
+  }
+}
+
+impl<S, F> Observable for OnUnsubscribeOp<S, F>
+where
+  S: Observable,
+  F: FnMut(),
+{
+  type Item = S::Item;
+  type Err = S::Err;
+}
+
+impl<'a, S, F> LocalObservable<'a> for OnUnsubscribeOp<S, F>
+where
+  S: LocalObservable<'a>,
+  F: FnMut() + 'static,
+{
+  type Unsub = LocalSubscription;
+  on_unsubscribe_observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, F> SharedObservable for OnUnsubscribeOp<S, F>
+where
+  S: SharedObservable,
+  F: FnMut() + Send + Sync + 'static,
+  S::Unsub: Send + Sync,
+{
+  type Unsub = SharedSubscription;
+  on_unsubscribe_observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct OnUnsubscribeObserver<O, U> {
+  observer: O,
+  // Closed as soon as the source terminates, so a later teardown of the
+  // whole subscription doesn't count as an unsubscribe.
+  hook: U,
+}
+
+impl<Item, Err, O, U> Observer<Item, Err> for OnUnsubscribeObserver<O, U>
+where
+  O: Observer<Item, Err>,
+  U: SubscriptionLike,
+{
+  #[inline]
+  fn next(&mut self, value: Item) { self.observer.next(value) }
+
+  fn error(&mut self, err: Err) {
+    self.hook.unsubscribe();
+    self.observer.error(err);
+  }
+
+  fn complete(&mut self) {
+    self.hook.unsubscribe();
+    self.observer.complete();
+  }
+}
+
+struct UnsubscribeHook<Sub, F> {
+  s: Sub,
+  f: F,
+}
+
+impl<Sub, F> SubscriptionLike for UnsubscribeHook<Sub, F>
+where
+  Sub: SubscriptionLike,
+  F: FnMut(),
+{
+  fn unsubscribe(&mut self) {
+    if !self.s.is_closed() {
+      self.s.unsubscribe();
+      (self.f)()
+    }
+  }
+
+  fn is_closed(&self) -> bool { self.s.is_closed() }
+
+  fn inner_addr(&self) -> *const () { self.s.inner_addr() }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use std::cell::{Cell, RefCell};
+  use std::rc::Rc;
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn tap_all_notifications() {
+    let log = RefCell::new(vec![]);
+    let mut emitted = vec![];
+    observable::from_iter(0..3)
+      .tap(ObserverAll::new(
+        |v: &i32| log.borrow_mut().push(format!("next {}", v)),
+        |_: &()| log.borrow_mut().push("error".to_owned()),
+        || log.borrow_mut().push("complete".to_owned()),
+      ))
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(emitted, vec![0, 1, 2]);
+    assert_eq!(
+      *log.borrow(),
+      vec!["next 0", "next 1", "next 2", "complete"]
+    );
+  }
+
+  #[test]
+  fn tap_next_does_not_change_stream() {
+    let mut tapped = 0;
+    let mut emitted = vec![];
+    observable::from_iter(vec![String::from("a"), String::from("b")])
+      .tap_next(|v| tapped += v.len())
+      .subscribe(|v| emitted.push(v));
+
+    assert_eq!(tapped, 2);
+    assert_eq!(emitted, vec!["a", "b"]);
+  }
+
+  #[test]
+  fn tap_error_and_complete() {
+    let mut tapped_err = None;
+    let mut tapped_complete = 0;
+    observable::throw("oops")
+      .tap_error(|e| tapped_err = Some(*e))
+      .tap_complete(|| tapped_complete += 1)
+      .subscribe_err(|_| {}, |_| {});
+    assert_eq!(tapped_err, Some("oops"));
+    assert_eq!(tapped_complete, 0);
+
+    observable::empty::<i32>()
+      .tap_complete(|| tapped_complete += 1)
+      .subscribe(|_| {});
+    assert_eq!(tapped_complete, 1);
+  }
+
+  #[test]
+  fn on_subscribe_every_time() {
+    let count = Rc::new(Cell::new(0));
+    let c_count = count.clone();
+    let o =
+      observable::of(1).on_subscribe(move || c_count.set(c_count.get() + 1));
+    o.clone().subscribe(|_| {});
+    o.subscribe(|_| {});
+    assert_eq!(count.get(), 2);
+  }
+
+  #[test]
+  fn on_unsubscribe_fires_on_unsubscribe() {
+    let unsubscribed = Rc::new(Cell::new(0));
+    let c_unsubscribed = unsubscribed.clone();
+    let mut subject = LocalSubject::new();
+    let mut subscription = subject
+      .clone()
+      .on_unsubscribe(move || c_unsubscribed.set(c_unsubscribed.get() + 1))
+      .subscribe(|_: i32| {});
+    subject.next(1);
+    assert_eq!(unsubscribed.get(), 0);
+    subscription.unsubscribe();
+    subscription.unsubscribe();
+    assert_eq!(unsubscribed.get(), 1);
+  }
+
+  #[test]
+  fn on_unsubscribe_not_fired_on_termination() {
+    let unsubscribed = Rc::new(Cell::new(false));
+    let c_unsubscribed = unsubscribed.clone();
+    let mut subscription = observable::from_iter(0..10)
+      .on_unsubscribe(move || c_unsubscribed.set(true))
+      .subscribe(|_| {});
+    subscription.unsubscribe();
+    assert!(!unsubscribed.get());
+
+    let c_unsubscribed = unsubscribed.clone();
+    let mut subject: LocalSubject<i32, ()> = LocalSubject::new();
+    subject
+      .clone()
+      .on_unsubscribe(move || c_unsubscribed.set(true))
+      .subscribe_err(|_| {}, |_| {});
+    subject.error(());
+    assert!(!unsubscribed.get());
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let unsubscribed = Arc::new(Mutex::new(false));
+    let c_unsubscribed = unsubscribed.clone();
+    let mut subject = SharedSubject::new();
+    let mut subscription = subject
+      .clone()
+      .tap_next(|_: &i32| {})
+      .on_subscribe(|| {})
+      .on_unsubscribe(move || *c_unsubscribed.lock().unwrap() = true)
+      .clone()
+      .to_shared()
+      .subscribe(|_| {});
+    subject.next(1);
+    subscription.unsubscribe();
+    assert!(*unsubscribed.lock().unwrap());
+  }
+}
@@ -1,5 +1,11 @@
+use crate::observable::interval::SpawnHandle;
 use crate::prelude::*;
+use futures::prelude::*;
+use futures::task::{LocalSpawn, LocalSpawnExt};
+use futures_timer::Delay;
 use observable::observable_proxy_impl;
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -171,6 +177,126 @@ where
   }
 }
 
+#[derive(Clone)]
+pub struct LocalThrottleTimeOp<S, Sp> {
+  pub(crate) source: S,
+  pub(crate) duration: Duration,
+  pub(crate) edge: ThrottleEdge,
+  pub(crate) spawner: Sp,
+}
+
+observable_proxy_impl!(LocalThrottleTimeOp, S, Sp);
+
+impl<S, Sp> LocalObservable<'static> for LocalThrottleTimeOp<S, Sp>
+where
+  S: LocalObservable<'static>,
+  S::Item: Clone + 'static,
+  Sp: LocalSpawn + 'static,
+{
+  type Unsub = S::Unsub;
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + 'static>(
+    self,
+    subscriber: Subscriber<O, LocalSubscription>,
+  ) -> Self::Unsub {
+    let Self {
+      source,
+      duration,
+      edge,
+      spawner,
+    } = self;
+    let Subscriber {
+      observer,
+      subscription,
+    } = subscriber;
+    source.actual_subscribe(Subscriber {
+      observer: LocalThrottleTimeObserver {
+        observer: Rc::new(RefCell::new(observer)),
+        state: Rc::new(RefCell::new(LocalThrottleState {
+          edge,
+          delay: duration,
+          trailing_value: None,
+          throttled: None,
+          subscription: subscription.clone(),
+          spawner,
+        })),
+      },
+      subscription,
+    })
+  }
+}
+
+struct LocalThrottleState<Item, Sp> {
+  edge: ThrottleEdge,
+  delay: Duration,
+  trailing_value: Option<Item>,
+  throttled: Option<LocalSubscription>,
+  subscription: LocalSubscription,
+  spawner: Sp,
+}
+
+// The state is never borrowed while the observer is notified, so the observer
+// may feed back into the source.
+pub struct LocalThrottleTimeObserver<O, Item, Sp> {
+  observer: Rc<RefCell<O>>,
+  state: Rc<RefCell<LocalThrottleState<Item, Sp>>>,
+}
+
+impl<O, Item, Err, Sp> Observer<Item, Err>
+  for LocalThrottleTimeObserver<O, Item, Sp>
+where
+  O: Observer<Item, Err> + 'static,
+  Item: Clone + 'static,
+  Sp: LocalSpawn + 'static,
+{
+  fn next(&mut self, value: Item) {
+    let mut state = self.state.borrow_mut();
+    if state.edge == ThrottleEdge::Tailing {
+      state.trailing_value = Some(value.clone());
+    }
+
+    if state.throttled.is_none() {
+      let c_state = self.state.clone();
+      let c_observer = self.observer.clone();
+      let f = Delay::new(state.delay).map(move |_| {
+        let trailing_value = {
+          let mut state = c_state.borrow_mut();
+          // The future ends by itself, so its handle is only dropped.
+          if let Some(throttled) = state.throttled.take() {
+            state.subscription.remove(&throttled);
+          }
+          state.trailing_value.take()
+        };
+        if let Some(v) = trailing_value {
+          c_observer.borrow_mut().next(v);
+        }
+      });
+      let handle = state
+        .spawner
+        .spawn_local_with_handle(f)
+        .expect("spawn future for a throttle failed");
+      let mut throttled = LocalSubscription::default();
+      throttled.add(SpawnHandle::new(handle));
+      state.subscription.add(throttled.clone());
+      state.throttled = Some(throttled);
+      if state.edge == ThrottleEdge::Leading {
+        drop(state);
+        self.observer.borrow_mut().next(value);
+      }
+    }
+  }
+
+  fn error(&mut self, err: Err) { self.observer.borrow_mut().error(err) }
+
+  fn complete(&mut self) {
+    let trailing_value = self.state.borrow_mut().trailing_value.take();
+    let mut observer = self.observer.borrow_mut();
+    if let Some(value) = trailing_value {
+      observer.next(value);
+    }
+    observer.complete();
+  }
+}
+
 #[test]
 fn smoke() {
   let x = Arc::new(Mutex::new(vec![]));
@@ -214,3 +340,66 @@ fn fork_and_shared() {
     .to_shared()
     .subscribe(|_| {});
 }
+
+#[test]
+fn local() {
+  use futures::executor::LocalPool;
+  let mut pool = LocalPool::new();
+  let spawner = pool.spawner();
+  let values = Rc::new(RefCell::new(vec![]));
+  let throttle_subscribe = |edge, subject: LocalSubject<'static, i32, ()>| {
+    let c_values = values.clone();
+    subject
+      .local_throttle_time(Duration::from_millis(10), edge, spawner.clone())
+      .subscribe(move |v| c_values.borrow_mut().push(v));
+  };
+
+  // tailing throttle
+  let mut subject = LocalSubject::new();
+  throttle_subscribe(ThrottleEdge::Tailing, subject.clone());
+  subject.next(1);
+  subject.next(2);
+  assert!(values.borrow().is_empty());
+  pool.run();
+  subject.next(3);
+  pool.run();
+  assert_eq!(*values.borrow(), vec![2, 3]);
+
+  // leading throttle
+  values.borrow_mut().clear();
+  let mut subject = LocalSubject::new();
+  throttle_subscribe(ThrottleEdge::Leading, subject.clone());
+  subject.next(1);
+  subject.next(2);
+  assert_eq!(*values.borrow(), vec![1]);
+  pool.run();
+  subject.next(3);
+  assert_eq!(*values.borrow(), vec![1, 3]);
+}
+
+#[test]
+fn local_feed_back_into_source() {
+  use futures::executor::LocalPool;
+  let mut pool = LocalPool::new();
+  let values = Rc::new(RefCell::new(vec![]));
+  let c_values = values.clone();
+  let mut subject = LocalSubject::new();
+  let mut c_subject = subject.clone();
+  subject
+    .clone()
+    .local_throttle_time(
+      Duration::from_millis(1),
+      ThrottleEdge::Tailing,
+      pool.spawner(),
+    )
+    .subscribe(move |v| {
+      c_values.borrow_mut().push(v);
+      if v < 3 {
+        c_subject.next(v + 1);
+      }
+    });
+  subject.next(0);
+  // Each value is emitted once its window ended, and opens a new one.
+  pool.run();
+  assert_eq!(*values.borrow(), vec![0, 1, 2, 3]);
+}
@@ -0,0 +1,137 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+use std::time::{Duration, Instant};
+
+/// A value emitted by [`time_interval`](Observable::time_interval), paired
+/// with the time elapsed since the previous value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeInterval<Item> {
+  pub value: Item,
+  pub elapsed: Duration,
+}
+
+#[derive(Clone)]
+pub struct TimeIntervalOp<S, C> {
+  pub(crate) source: S,
+  pub(crate) clock: C,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    // The first value measures the time elapsed since subscription.
+    let last = self.clock.now();
+    self.source.actual_subscribe(Subscriber {
+      observer: TimeIntervalObserver {
+        observer: subscriber.observer,
+        clock: self.clock,
+        last,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, C> Observable for TimeIntervalOp<S, C>
+where
+  S: Observable,
+  C: Clock,
+{
+  type Item = TimeInterval<S::Item>;
+  type Err = S::Err;
+}
+
+impl<'a, S, C> LocalObservable<'a> for TimeIntervalOp<S, C>
+where
+  S: LocalObservable<'a>,
+  C: Clock + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, C> SharedObservable for TimeIntervalOp<S, C>
+where
+  S: SharedObservable,
+  C: Clock + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct TimeIntervalObserver<O, C> {
+  observer: O,
+  clock: C,
+  last: Instant,
+}
+
+impl<Item, Err, O, C> Observer<Item, Err> for TimeIntervalObserver<O, C>
+where
+  O: Observer<TimeInterval<Item>, Err>,
+  C: Clock,
+{
+  fn next(&mut self, value: Item) {
+    let now = self.clock.now();
+    let elapsed = now.saturating_duration_since(self.last);
+    self.last = now;
+    self.observer.next(TimeInterval { value, elapsed })
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::ops::time_interval::TimeInterval;
+  use crate::prelude::*;
+  use std::time::Duration;
+
+  #[test]
+  fn virtual_time() {
+    let clock = VirtualClock::default();
+    let mut emitted = vec![];
+    {
+      let mut subject = Subject::new();
+      subject
+        .clone()
+        .time_interval_with(clock.clone())
+        .subscribe(|v| emitted.push(v));
+
+      clock.advance(Duration::from_millis(3));
+      subject.next('a');
+      clock.advance(Duration::from_millis(10));
+      subject.next('b');
+      subject.next('c');
+    }
+
+    assert_eq!(
+      emitted,
+      vec![
+        TimeInterval {
+          value: 'a',
+          elapsed: Duration::from_millis(3)
+        },
+        TimeInterval {
+          value: 'b',
+          elapsed: Duration::from_millis(10)
+        },
+        TimeInterval {
+          value: 'c',
+          elapsed: Duration::from_millis(0)
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).time_interval();
+    o.clone().subscribe(|_| {});
+    o.time_interval_with(VirtualClock::default())
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
@@ -0,0 +1,132 @@
+use crate::observer::{complete_proxy_impl, error_proxy_impl};
+use crate::prelude::*;
+use std::time::Instant;
+
+/// A value emitted by [`timestamp`](Observable::timestamp), paired with the
+/// time it was emitted at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timestamped<Item> {
+  pub value: Item,
+  pub at: Instant,
+}
+
+#[derive(Clone)]
+pub struct TimestampOp<S, C> {
+  pub(crate) source: S,
+  pub(crate) clock: C,
+}
+
+#[doc(hidden)]
+macro observable_impl($subscription:ty, $($marker:ident +)* $lf: lifetime) {
+  fn actual_subscribe<O: Observer<Self::Item, Self::Err> + $($marker +)* $lf>(
+    self,
+    subscriber: Subscriber<O, $subscription>,
+  ) -> Self::Unsub {
+    self.source.actual_subscribe(Subscriber {
+      observer: TimestampObserver {
+        observer: subscriber.observer,
+        clock: self.clock,
+      },
+      subscription: subscriber.subscription,
+    })
+  }
+}
+
+impl<S, C> Observable for TimestampOp<S, C>
+where
+  S: Observable,
+  C: Clock,
+{
+  type Item = Timestamped<S::Item>;
+  type Err = S::Err;
+}
+
+impl<'a, S, C> LocalObservable<'a> for TimestampOp<S, C>
+where
+  S: LocalObservable<'a>,
+  C: Clock + 'a,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(LocalSubscription, 'a);
+}
+
+impl<S, C> SharedObservable for TimestampOp<S, C>
+where
+  S: SharedObservable,
+  C: Clock + Send + Sync + 'static,
+{
+  type Unsub = S::Unsub;
+  observable_impl!(SharedSubscription, Send + Sync + 'static);
+}
+
+pub struct TimestampObserver<O, C> {
+  observer: O,
+  clock: C,
+}
+
+impl<Item, Err, O, C> Observer<Item, Err> for TimestampObserver<O, C>
+where
+  O: Observer<Timestamped<Item>, Err>,
+  C: Clock,
+{
+  fn next(&mut self, value: Item) {
+    let at = self.clock.now();
+    self.observer.next(Timestamped { value, at })
+  }
+  error_proxy_impl!(Err, observer);
+  complete_proxy_impl!(observer);
+}
+
+#[cfg(test)]
+mod test {
+  use crate::ops::timestamp::Timestamped;
+  use crate::prelude::*;
+  use std::time::Duration;
+
+  #[test]
+  fn virtual_time() {
+    let clock = VirtualClock::default();
+    let start = clock.now();
+    let mut emitted = vec![];
+    {
+      let mut subject = Subject::new();
+      subject
+        .clone()
+        .timestamp_with(clock.clone())
+        .subscribe(|v| emitted.push(v));
+
+      subject.next(1);
+      clock.advance(Duration::from_millis(10));
+      subject.next(2);
+      clock.advance(Duration::from_millis(5));
+      subject.next(3);
+    }
+
+    assert_eq!(
+      emitted,
+      vec![
+        Timestamped {
+          value: 1,
+          at: start
+        },
+        Timestamped {
+          value: 2,
+          at: start + Duration::from_millis(10)
+        },
+        Timestamped {
+          value: 3,
+          at: start + Duration::from_millis(15)
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn fork_and_shared() {
+    let o = observable::from_iter(0..10).timestamp();
+    o.clone().subscribe(|_| {});
+    o.timestamp_with(VirtualClock::default())
+      .to_shared()
+      .subscribe(|_| {});
+  }
+}
@@ -2,11 +2,17 @@ use crate::prelude::*;
 mod thread_scheduler;
 use thread_scheduler::new_thread_schedule;
 mod thread_pool_scheduler;
+mod clock;
+pub use clock::{Clock, SystemClock, VirtualClock};
+mod local_scheduler;
+pub use local_scheduler::LocalScheduler;
+mod manual_scheduler;
+pub use manual_scheduler::ManualScheduler;
 use crate::observable::{from_future::DEFAULT_RUNTIME, interval::SpawnHandle};
 use futures::prelude::*;
 use futures::task::SpawnExt;
 use futures_timer::Delay;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thread_pool_scheduler::thread_pool_schedule;
 
 /// A Scheduler is an object to order task and schedule their execution.
@@ -17,8 +23,27 @@ pub trait Scheduler {
     delay: Option<Duration>,
     state: T,
   ) -> SharedSubscription;
+
+  /// The current time, which the delays of the scheduled tasks count from.
+  /// A scheduler running on a virtual time reports that instead.
+  fn now(&self) -> Instant { Instant::now() }
+}
+
+/// A Scheduler which runs its tasks on the thread they are scheduled from, so
+/// neither the tasks nor their state have to be `Send`.
+pub trait LocalSchedulerLike {
+  fn schedule<T: 'static>(
+    &self,
+    task: impl FnOnce(LocalSubscription, T) + 'static,
+    delay: Option<Duration>,
+    state: T,
+  ) -> LocalSubscription;
+
+  /// The current time, which the delays of the scheduled tasks count from.
+  fn now(&self) -> Instant { Instant::now() }
 }
 
+#[derive(Clone, Copy)]
 pub enum Schedulers {
   /// NewThread Scheduler always creates a new thread for each unit of work.
   NewThread,
@@ -0,0 +1,61 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of the current time.
+///
+/// Time aware operators read the time from a `Clock` instead of calling
+/// `Instant::now()` directly, so they can be driven by a [`VirtualClock`] in
+/// tests and produce deterministic values.
+pub trait Clock {
+  fn now(&self) -> Instant;
+}
+
+/// The clock of the operating system, backed by `Instant::now()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  #[inline]
+  fn now(&self) -> Instant { Instant::now() }
+}
+
+/// A clock which only moves when told to. Clones share the same time.
+#[derive(Clone, Debug)]
+pub struct VirtualClock(Arc<Mutex<Instant>>);
+
+impl VirtualClock {
+  pub fn new(start: Instant) -> Self {
+    VirtualClock(Arc::new(Mutex::new(start)))
+  }
+
+  /// Moves the clock forward by `dur`.
+  pub fn advance(&self, dur: Duration) { *self.0.lock().unwrap() += dur; }
+
+  /// Sets the clock to `at`.
+  pub fn set(&self, at: Instant) { *self.0.lock().unwrap() = at; }
+}
+
+impl Default for VirtualClock {
+  fn default() -> Self { VirtualClock::new(Instant::now()) }
+}
+
+impl Clock for VirtualClock {
+  #[inline]
+  fn now(&self) -> Instant { *self.0.lock().unwrap() }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn virtual_clock_shared_by_clones() {
+    let start = Instant::now();
+    let clock = VirtualClock::new(start);
+    let c_clock = clock.clone();
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(c_clock.now(), start + Duration::from_secs(1));
+    c_clock.set(start);
+    assert_eq!(clock.now(), start);
+  }
+}
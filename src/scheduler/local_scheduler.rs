@@ -0,0 +1,255 @@
+use crate::observable::interval::SpawnHandle;
+use crate::prelude::*;
+use crate::scheduler::LocalSchedulerLike;
+use futures::executor::{LocalPool, LocalSpawner};
+use futures::future::{self, Either, FutureExt, LocalFutureObj};
+use futures::task::{LocalSpawn, LocalSpawnExt, SpawnError};
+use futures_timer::Delay;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// A single-threaded scheduler running its tasks on a
+/// `futures::executor::LocalPool`.
+///
+/// Nothing runs until the pool is driven by [`run`](LocalScheduler::run),
+/// [`run_until_stalled`](LocalScheduler::run_until_stalled) or
+/// [`try_run_one`](LocalScheduler::try_run_one), so an application with its
+/// own main loop decides when timers fire and `observe_on` delivers. Tasks
+/// without a delay run in the order they were scheduled. Clones share the same
+/// pool, and as it also implements `LocalSpawn`, it can drive
+/// [`local_interval`](crate::observable::local_interval) and the other local
+/// time operators too.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let scheduler = LocalScheduler::new();
+/// let values = Rc::new(RefCell::new(vec![]));
+/// let c_values = values.clone();
+/// let mut subject = LocalSubject::new();
+/// subject
+///   .clone()
+///   .observe_on(scheduler.clone())
+///   .subscribe(move |v| c_values.borrow_mut().push(v));
+///
+/// subject.next(1);
+/// subject.next(2);
+/// assert!(values.borrow().is_empty());
+/// scheduler.run_until_stalled();
+/// assert_eq!(*values.borrow(), vec![1, 2]);
+/// ```
+#[derive(Clone)]
+pub struct LocalScheduler {
+  pool: Rc<RefCell<LocalPool>>,
+  spawner: LocalSpawner,
+}
+
+impl LocalScheduler {
+  pub fn new() -> Self {
+    let pool = LocalPool::new();
+    let spawner = pool.spawner();
+    LocalScheduler {
+      pool: Rc::new(RefCell::new(pool)),
+      spawner,
+    }
+  }
+
+  /// Runs all tasks, including the ones they schedule, until none is left.
+  /// Blocks the thread while waiting for delayed tasks.
+  ///
+  /// # Panics
+  ///
+  /// Panics if called from a task running on this scheduler.
+  pub fn run(&self) { self.pool.borrow_mut().run() }
+
+  /// Runs all tasks that are ready, and returns once the remaining ones wait
+  /// for their delay. Never blocks the thread.
+  ///
+  /// # Panics
+  ///
+  /// Panics if called from a task running on this scheduler.
+  pub fn run_until_stalled(&self) { self.pool.borrow_mut().run_until_stalled() }
+
+  /// Runs the tasks that are ready until one of them is done, and returns
+  /// whether that happened. Never blocks the thread.
+  ///
+  /// # Panics
+  ///
+  /// Panics if called from a task running on this scheduler.
+  pub fn try_run_one(&self) -> bool { self.pool.borrow_mut().try_run_one() }
+}
+
+impl Default for LocalScheduler {
+  fn default() -> Self { LocalScheduler::new() }
+}
+
+impl LocalSchedulerLike for LocalScheduler {
+  fn schedule<T: 'static>(
+    &self,
+    task: impl FnOnce(LocalSubscription, T) + 'static,
+    delay: Option<Duration>,
+    state: T,
+  ) -> LocalSubscription {
+    let mut subscription = LocalSubscription::default();
+    let c_subscription = subscription.clone();
+    // A task without delay doesn't wait for the timer thread, so it runs as
+    // soon as the pool is driven.
+    let wait = match delay {
+      Some(delay) => Either::Left(Delay::new(delay).map(|_| ())),
+      None => Either::Right(future::ready(())),
+    };
+    let f = wait.map(move |_| {
+      if !c_subscription.is_closed() {
+        task(c_subscription, state)
+      }
+    });
+    let handle = self
+      .spawner
+      .clone()
+      .spawn_local_with_handle(f)
+      .expect("spawn task to local pool failed.");
+    subscription.add(SpawnHandle::new(handle));
+    subscription
+  }
+}
+
+impl LocalSpawn for LocalScheduler {
+  #[inline]
+  fn spawn_local_obj(
+    &mut self,
+    future: LocalFutureObj<'static, ()>,
+  ) -> Result<(), SpawnError> {
+    self.spawner.spawn_local_obj(future)
+  }
+
+  #[inline]
+  fn status_local(&self) -> Result<(), SpawnError> {
+    self.spawner.status_local()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use crate::scheduler::LocalSchedulerLike;
+  use std::cell::{Cell, RefCell};
+  use std::rc::Rc;
+  use std::time::Duration;
+
+  #[test]
+  fn observe_on_keeps_order() {
+    let scheduler = LocalScheduler::new();
+    let values = Rc::new(RefCell::new(vec![]));
+    let completed = Rc::new(Cell::new(false));
+    let c_values = values.clone();
+    let c_completed = completed.clone();
+    observable::from_iter(0..3)
+      .observe_on(scheduler.clone())
+      .subscribe_complete(
+        move |v| c_values.borrow_mut().push(v),
+        move || c_completed.set(true),
+      );
+    assert!(values.borrow().is_empty());
+    assert!(scheduler.try_run_one());
+    assert_eq!(*values.borrow(), vec![0]);
+    scheduler.run_until_stalled();
+    assert_eq!(*values.borrow(), vec![0, 1, 2]);
+    assert!(completed.get());
+    assert!(!scheduler.try_run_one());
+  }
+
+  #[test]
+  fn observe_on_unsubscribe() {
+    let scheduler = LocalScheduler::new();
+    let emitted = Rc::new(Cell::new(false));
+    let c_emitted = emitted.clone();
+    observable::of(1)
+      .observe_on(scheduler.clone())
+      .subscribe(move |_| c_emitted.set(true))
+      .unsubscribe();
+    scheduler.run();
+    assert!(!emitted.get());
+  }
+
+  #[test]
+  fn observe_on_feed_back_into_source() {
+    let scheduler = LocalScheduler::new();
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    let mut subject = LocalSubject::new();
+    let mut c_subject = subject.clone();
+    subject
+      .clone()
+      .observe_on(scheduler.clone())
+      .subscribe(move |v| {
+        c_values.borrow_mut().push(v);
+        if v < 3 {
+          c_subject.next(v + 1);
+        }
+      });
+    subject.next(0);
+    scheduler.run_until_stalled();
+    assert_eq!(*values.borrow(), vec![0, 1, 2, 3]);
+  }
+
+  #[test]
+  fn timer() {
+    let scheduler = LocalScheduler::new();
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    observable::timer(Duration::from_millis(10), scheduler.clone())
+      .subscribe(move |v| c_values.borrow_mut().push(v));
+    scheduler.run_until_stalled();
+    assert!(values.borrow().is_empty());
+    scheduler.run();
+    assert_eq!(*values.borrow(), vec![0]);
+  }
+
+  #[test]
+  fn timer_periodic() {
+    let scheduler = LocalScheduler::new();
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    observable::timer_periodic(
+      Duration::from_millis(1),
+      Duration::from_millis(1),
+      scheduler.clone(),
+    )
+    .take(3)
+    .subscribe(move |v| c_values.borrow_mut().push(v));
+    scheduler.run();
+    assert_eq!(*values.borrow(), vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn delayed_tasks_wait() {
+    let scheduler = LocalScheduler::new();
+    let order = Rc::new(RefCell::new(vec![]));
+    let c_order = order.clone();
+    scheduler.schedule(
+      move |_, order: Rc<RefCell<Vec<_>>>| order.borrow_mut().push("late"),
+      Some(Duration::from_millis(5)),
+      order.clone(),
+    );
+    scheduler.schedule(move |_, _| c_order.borrow_mut().push("now"), None, ());
+    scheduler.run();
+    assert_eq!(*order.borrow(), vec!["now", "late"]);
+  }
+
+  #[test]
+  fn spawn_local_operators() {
+    let scheduler = LocalScheduler::new();
+    let values = Rc::new(RefCell::new(vec![]));
+    let c_values = values.clone();
+    observable::local_interval(Duration::from_millis(1), scheduler.clone())
+      .take(2)
+      .local_delay(Duration::from_millis(1), scheduler.clone())
+      .subscribe(move |v| c_values.borrow_mut().push(v));
+    scheduler.run();
+    assert_eq!(*values.borrow(), vec![0, 1]);
+  }
+}
@@ -0,0 +1,341 @@
+use crate::prelude::*;
+use crate::scheduler::Scheduler;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A Scheduler which only queues its tasks, until they are run by calling
+/// [`run_pending`](ManualScheduler::run_pending).
+///
+/// Tasks can be scheduled from any thread, but only run on the thread which
+/// calls `run_pending`, such as the main thread of a host application calling
+/// back from its idle or timer callback. Combined with `observe_on`, values
+/// emitted on worker threads are delivered on that thread. Delays count from
+/// the time of the `clock`, so a [`VirtualClock`] makes the scheduler run on a
+/// virtual time. Clones share the same queue.
+///
+/// # Example
+///
+/// ```
+/// use rxrust::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::{thread, time::Instant};
+///
+/// let main_thread = ManualScheduler::new();
+/// let values = Arc::new(Mutex::new(vec![]));
+/// let c_values = values.clone();
+/// observable::from_iter(0..3)
+///   .subscribe_on(Schedulers::NewThread)
+///   .to_shared()
+///   .observe_on(main_thread.clone())
+///   .to_shared()
+///   .subscribe(move |v| {
+///     c_values.lock().unwrap().push((v, thread::current().id()))
+///   });
+///
+/// // The host's idle callback.
+/// while values.lock().unwrap().len() < 3 {
+///   main_thread.run_pending(Instant::now());
+/// }
+/// let id = thread::current().id();
+/// assert_eq!(*values.lock().unwrap(), vec![(0, id), (1, id), (2, id)]);
+/// ```
+#[derive(Clone)]
+pub struct ManualScheduler<C = SystemClock> {
+  clock: C,
+  queue: Arc<Mutex<TaskQueue>>,
+}
+
+impl ManualScheduler {
+  pub fn new() -> Self { ManualScheduler::with_clock(SystemClock) }
+}
+
+impl Default for ManualScheduler {
+  fn default() -> Self { ManualScheduler::new() }
+}
+
+impl<C: Clock> ManualScheduler<C> {
+  /// Creates a scheduler reading the time from `clock`.
+  pub fn with_clock(clock: C) -> Self {
+    ManualScheduler {
+      clock,
+      queue: Arc::new(Mutex::new(TaskQueue::default())),
+    }
+  }
+
+  /// Runs the tasks which were scheduled before the call and are due at
+  /// `now`, in the order they are due, and returns how many of them ran.
+  /// Tasks that the running ones schedule wait for the next call, so a single
+  /// call always ends.
+  pub fn run_pending(&self, now: Instant) -> usize {
+    let last = self.queue.lock().unwrap().next_seq;
+    let mut count = 0;
+    // Tasks scheduled during this call may be due before older ones, so they
+    // are set aside until the older ones ran.
+    let mut newer = vec![];
+    loop {
+      let task = {
+        let mut queue = self.queue.lock().unwrap();
+        queue.drop_cancelled();
+        match queue.tasks.peek() {
+          Some(task) if task.due <= now => queue.tasks.pop(),
+          _ => None,
+        }
+      };
+      match task {
+        Some(task) if task.seq >= last => newer.push(task),
+        Some(task) => {
+          if !task.subscription.is_closed() {
+            (task.run)();
+            count += 1;
+          }
+        }
+        None => break,
+      }
+    }
+    self.queue.lock().unwrap().tasks.extend(newer);
+    count
+  }
+
+  /// The time the earliest queued task is due at, if there is any. Tasks
+  /// which were unsubscribed from don't count.
+  pub fn next_due(&self) -> Option<Instant> {
+    let mut queue = self.queue.lock().unwrap();
+    queue.drop_cancelled();
+    queue.tasks.peek().map(|task| task.due)
+  }
+}
+
+impl<C: Clock> Scheduler for ManualScheduler<C> {
+  fn schedule<T: Send + 'static>(
+    &self,
+    task: impl FnOnce(SharedSubscription, T) + Send + 'static,
+    delay: Option<Duration>,
+    state: T,
+  ) -> SharedSubscription {
+    let subscription = SharedSubscription::default();
+    let c_subscription = subscription.clone();
+    let due = self.clock.now() + delay.unwrap_or_default();
+    let mut queue = self.queue.lock().unwrap();
+    let seq = queue.next_seq;
+    queue.next_seq += 1;
+    queue.tasks.push(QueuedTask {
+      due,
+      seq,
+      subscription: subscription.clone(),
+      run: Box::new(move || task(c_subscription, state)),
+    });
+    subscription
+  }
+
+  fn now(&self) -> Instant { self.clock.now() }
+}
+
+#[derive(Default)]
+struct TaskQueue {
+  next_seq: u64,
+  tasks: BinaryHeap<QueuedTask>,
+}
+
+impl TaskQueue {
+  /// Drops the earliest tasks as long as they were unsubscribed from, so they
+  /// don't pile up until they are due.
+  fn drop_cancelled(&mut self) {
+    while let Some(task) = self.tasks.peek() {
+      if !task.subscription.is_closed() {
+        break;
+      }
+      self.tasks.pop();
+    }
+  }
+}
+
+struct QueuedTask {
+  due: Instant,
+  // Orders the tasks due at the same time by when they were scheduled.
+  seq: u64,
+  subscription: SharedSubscription,
+  run: Box<dyn FnOnce() + Send>,
+}
+
+// `BinaryHeap` is a max-heap, so the earliest task has to be the greatest.
+impl Ord for QueuedTask {
+  fn cmp(&self, other: &Self) -> Ordering {
+    (other.due, other.seq).cmp(&(self.due, self.seq))
+  }
+}
+
+impl PartialOrd for QueuedTask {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl PartialEq for QueuedTask {
+  fn eq(&self, other: &Self) -> bool { self.seq == other.seq }
+}
+
+impl Eq for QueuedTask {}
+
+#[cfg(test)]
+mod test {
+  use crate::prelude::*;
+  use crate::scheduler::Scheduler;
+  use std::sync::{Arc, Mutex};
+  use std::thread;
+  use std::time::Duration;
+
+  #[test]
+  fn runs_only_when_pumped() {
+    let scheduler = ManualScheduler::with_clock(VirtualClock::default());
+    let order = Arc::new(Mutex::new(vec![]));
+    for &(name, delay) in &[("b", 2), ("a", 1), ("c", 2), ("now", 0)] {
+      let order = order.clone();
+      let c_scheduler = scheduler.clone();
+      scheduler.schedule(
+        move |_, _| {
+          order.lock().unwrap().push(name);
+          if name == "a" {
+            // Due right away, before "b" and "c".
+            let order = order.clone();
+            c_scheduler.schedule(
+              move |_, _| order.lock().unwrap().push("after a"),
+              None,
+              (),
+            );
+          }
+        },
+        Some(Duration::from_secs(delay)),
+        (),
+      );
+    }
+    let now = scheduler.now();
+    assert_eq!(scheduler.run_pending(now), 1);
+    assert_eq!(scheduler.next_due(), Some(now + Duration::from_secs(1)));
+    assert_eq!(scheduler.run_pending(now + Duration::from_secs(5)), 3);
+    assert_eq!(*order.lock().unwrap(), vec!["now", "a", "b", "c"]);
+    assert_eq!(scheduler.next_due(), Some(now));
+    assert_eq!(scheduler.run_pending(now + Duration::from_secs(5)), 1);
+    assert_eq!(
+      *order.lock().unwrap(),
+      vec!["now", "a", "b", "c", "after a"]
+    );
+    assert_eq!(scheduler.next_due(), None);
+  }
+
+  #[test]
+  fn skips_unsubscribed() {
+    let scheduler = ManualScheduler::new();
+    let ran = Arc::new(Mutex::new(false));
+    let c_ran = ran.clone();
+    scheduler
+      .schedule(move |_, _| *c_ran.lock().unwrap() = true, None, ())
+      .unsubscribe();
+    assert_eq!(scheduler.run_pending(scheduler.now()), 0);
+    assert!(!*ran.lock().unwrap());
+  }
+
+  #[test]
+  fn next_due_ignores_unsubscribed() {
+    let scheduler = ManualScheduler::with_clock(VirtualClock::default());
+    let now = scheduler.now();
+    scheduler
+      .schedule(|_, _| {}, Some(Duration::from_secs(1)), ())
+      .unsubscribe();
+    scheduler.schedule(|_, _| {}, Some(Duration::from_secs(5)), ());
+    assert_eq!(scheduler.next_due(), Some(now + Duration::from_secs(5)));
+    assert_eq!(scheduler.queue.lock().unwrap().tasks.len(), 1);
+  }
+
+  #[test]
+  fn observe_on_from_worker_thread() {
+    let scheduler = ManualScheduler::new();
+    let values = Arc::new(Mutex::new(vec![]));
+    let completed = Arc::new(Mutex::new(false));
+    let c_values = values.clone();
+    let c_completed = completed.clone();
+    let mut subject = SharedSubject::new();
+    subject
+      .clone()
+      .to_shared()
+      .observe_on(scheduler.clone())
+      .to_shared()
+      .subscribe_complete(
+        move |v| c_values.lock().unwrap().push((v, thread::current().id())),
+        move || *c_completed.lock().unwrap() = true,
+      );
+    thread::spawn(move || {
+      subject.next(1);
+      subject.next(2);
+      subject.complete();
+    })
+    .join()
+    .unwrap();
+
+    assert!(values.lock().unwrap().is_empty());
+    assert_eq!(scheduler.run_pending(scheduler.now()), 3);
+    let id = thread::current().id();
+    assert_eq!(*values.lock().unwrap(), vec![(1, id), (2, id)]);
+    assert!(*completed.lock().unwrap());
+  }
+
+  #[test]
+  fn observe_on_forgets_delivered_notifications() {
+    let scheduler = ManualScheduler::new();
+    let mut subject = SharedSubject::new();
+    let subscription = subject
+      .clone()
+      .to_shared()
+      .observe_on(scheduler.clone())
+      .to_shared()
+      .subscribe(|_: i32| {})
+      .0;
+    let size = subscription.teardown_size();
+    for i in 0..10 {
+      subject.next(i);
+    }
+    assert_eq!(subscription.teardown_size(), size + 10);
+    assert_eq!(scheduler.run_pending(scheduler.now()), 10);
+    assert_eq!(subscription.teardown_size(), size);
+  }
+
+  #[test]
+  fn observe_on_unsubscribe() {
+    let scheduler = ManualScheduler::new();
+    let emitted = Arc::new(Mutex::new(false));
+    let c_emitted = emitted.clone();
+    observable::of(1)
+      .to_shared()
+      .observe_on(scheduler.clone())
+      .to_shared()
+      .subscribe(move |_| *c_emitted.lock().unwrap() = true)
+      .unsubscribe();
+    scheduler.run_pending(scheduler.now());
+    assert!(!*emitted.lock().unwrap());
+  }
+
+  #[test]
+  fn rescheduled_tasks_wait_for_next_run() {
+    let clock = VirtualClock::default();
+    let scheduler = ManualScheduler::with_clock(clock.clone());
+    let values = Arc::new(Mutex::new(vec![]));
+    let c_values = values.clone();
+    observable::timer_periodic(
+      Duration::from_secs(1),
+      Duration::from_secs(1),
+      scheduler.clone(),
+    )
+    .to_shared()
+    .subscribe(move |v| c_values.lock().unwrap().push(v));
+
+    clock.advance(Duration::from_secs(3));
+    scheduler.run_pending(clock.now());
+    assert_eq!(*values.lock().unwrap(), vec![0]);
+    scheduler.run_pending(clock.now());
+    scheduler.run_pending(clock.now());
+    assert_eq!(*values.lock().unwrap(), vec![0, 1, 2]);
+    scheduler.run_pending(clock.now());
+    assert_eq!(*values.lock().unwrap(), vec![0, 1, 2]);
+  }
+}
@@ -1,5 +1,13 @@
 use crate::prelude::*;
+use observable::blocking::{BlockingError, BlockingIter};
+use observable::into_stream::{
+  ObservableStream, SharedObservableStream, StreamState,
+};
 use observable::observable_proxy_impl;
+use observable::to_future::{ObservableFuture, SharedObservableFuture};
+use ops::take::TakeOp;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Shared wrap the Observable， subscribe and accept subscribe in a safe mode
 /// by SharedObservable.
@@ -23,6 +31,159 @@ pub trait SharedObservable: Observable {
   {
     Shared(self)
   }
+
+  /// Converts to a [`Stream`](futures::stream::Stream) of the notifications
+  /// of this Observable. See
+  /// [`ObservableStream`](crate::observable::into_stream::ObservableStream)
+  /// for how it behaves.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use futures::{executor::block_on, StreamExt};
+  /// use rxrust::prelude::*;
+  ///
+  /// let values: Vec<Result<_, ()>> =
+  ///   block_on(observable::from_iter(0..3).into_stream().collect());
+  /// assert_eq!(values, vec![Ok(0), Ok(1), Ok(2)]);
+  /// ```
+  #[inline]
+  fn into_stream(self) -> SharedObservableStream<Self>
+  where
+    Self: Sized,
+  {
+    ObservableStream::new(self, Arc::new(Mutex::new(StreamState::default())))
+  }
+
+  /// Converts to a [`Future`](futures::future::Future) resolving to the last
+  /// value of this Observable, once it completes. It's the same as
+  /// [`last_value`](SharedObservable::last_value).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use futures::executor::block_on;
+  /// use rxrust::prelude::*;
+  ///
+  /// let sum = block_on(observable::from_iter(0..4).sum().to_future());
+  /// assert_eq!(sum, Ok(6));
+  /// ```
+  #[inline]
+  fn to_future(self) -> SharedObservableFuture<Self>
+  where
+    Self: Sized,
+  {
+    self.last_value()
+  }
+
+  /// Converts to a [`Future`](futures::future::Future) resolving to the first
+  /// value of this Observable. See
+  /// [`ObservableFuture`](crate::observable::to_future::ObservableFuture) for
+  /// how it behaves.
+  #[inline]
+  fn first_value(self) -> SharedObservableFuture<TakeOp<Self>>
+  where
+    Self: Sized,
+  {
+    // Taken upstream, so a synchronous source stops after its first value.
+    ObservableFuture::new(self.first().into_stream(), true)
+  }
+
+  /// Converts to a [`Future`](futures::future::Future) resolving to the last
+  /// value of this Observable, once it completes. See
+  /// [`ObservableFuture`](crate::observable::to_future::ObservableFuture) for
+  /// how it behaves.
+  #[inline]
+  fn last_value(self) -> SharedObservableFuture<Self>
+  where
+    Self: Sized,
+  {
+    ObservableFuture::new(self.into_stream().bounded(1), false)
+  }
+
+  /// Converts to an [`Iterator`] of the notifications of this Observable,
+  /// blocking the current thread while waiting for the next one. See
+  /// [`BlockingIter`](crate::observable::blocking::BlockingIter) for how it
+  /// behaves.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let values: Vec<_> = observable::interval(Duration::from_millis(1))
+  ///   .take(3)
+  ///   .blocking_iter()
+  ///   .collect();
+  /// assert_eq!(values, vec![Ok(0), Ok(1), Ok(2)]);
+  /// ```
+  #[inline]
+  fn blocking_iter(self) -> BlockingIter<Self, Self::Item, Self::Err>
+  where
+    Self: Sized,
+  {
+    BlockingIter::new(self)
+  }
+
+  /// Subscribes with `next`, which is called on the current thread, and
+  /// blocks until this Observable completes or emits an error, which is
+  /// returned.
+  fn blocking_subscribe<N>(self, mut next: N) -> Result<(), Self::Err>
+  where
+    Self: Sized,
+    Self::Item: Send + 'static,
+    Self::Err: Send + 'static,
+    N: FnMut(Self::Item),
+  {
+    for item in self.blocking_iter() {
+      next(item?);
+    }
+    Ok(())
+  }
+
+  /// Blocks until this Observable emits its first value and returns it. Gives
+  /// up after `timeout`, if any.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use rxrust::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let first = observable::interval(Duration::from_millis(1))
+  ///   .blocking_first(Some(Duration::from_secs(1)));
+  /// assert_eq!(first, Ok(0));
+  /// ```
+  #[inline]
+  fn blocking_first(
+    self,
+    timeout: Option<Duration>,
+  ) -> Result<Self::Item, BlockingError<Self::Err>>
+  where
+    Self: Sized,
+    Self::Item: Send + 'static,
+    Self::Err: Send + 'static,
+  {
+    // Taken upstream, as subscribing to a synchronous source only returns
+    // once it's done.
+    self.first().blocking_iter().first(timeout)
+  }
+
+  /// Blocks until this Observable completes and returns its last value. Gives
+  /// up if it doesn't complete within `timeout`, if any.
+  #[inline]
+  fn blocking_last(
+    self,
+    timeout: Option<Duration>,
+  ) -> Result<Self::Item, BlockingError<Self::Err>>
+  where
+    Self: Sized,
+    Self::Item: Send + 'static,
+    Self::Err: Send + 'static,
+  {
+    self.blocking_iter().last(timeout)
+  }
 }
 
 pub trait SharedEmitter: Emitter {
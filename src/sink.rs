@@ -0,0 +1,414 @@
+//! Bridges between Observers and [`Sink`]s.
+//!
+//! Every Observer can be used as a [`Sink`] by wrapping it in an
+//! [`ObserverSink`], and subjects are sinks themselves. The other way around,
+//! a [`SinkObserver`] feeds the values it observes into a sink.
+use crate::prelude::*;
+use futures::prelude::*;
+use futures::task::{Context, Poll, Spawn, SpawnExt, Waker};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// A [`Sink`] passing every value sent to it to an Observer, and completing
+/// it when closed.
+///
+/// Sending never fails and never has to wait. An error can still be emitted
+/// by calling `error` on the observer directly.
+///
+/// # Example
+///
+/// ```
+/// use futures::{executor::block_on, SinkExt};
+/// use rxrust::{prelude::*, sink::ObserverSink};
+///
+/// let mut values = vec![];
+/// let mut completed = false;
+/// {
+///   let observer =
+///     observable::ObserverComp::new(|v| values.push(v), || completed = true);
+///   let mut sink = ObserverSink::new(observer);
+///   block_on(sink.send(1)).unwrap();
+///   block_on(sink.close()).unwrap();
+/// }
+/// assert_eq!(values, vec![1]);
+/// assert!(completed);
+/// ```
+pub struct ObserverSink<O, Err> {
+  observer: O,
+  _p: PhantomData<fn(Err)>,
+}
+
+impl<O, Err> ObserverSink<O, Err> {
+  /// Wraps `observer` into a sink.
+  pub fn new(observer: O) -> Self {
+    ObserverSink {
+      observer,
+      _p: PhantomData,
+    }
+  }
+
+  /// Gives back the observer.
+  pub fn into_inner(self) -> O { self.observer }
+}
+
+// The observer is never pinned.
+impl<O, Err> Unpin for ObserverSink<O, Err> {}
+
+#[doc(hidden)]
+macro sink_impl($item: ident) {
+  type Error = Infallible;
+
+  fn poll_ready(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<Result<(), Infallible>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn start_send(self: Pin<&mut Self>, item: $item) -> Result<(), Infallible> {
+    self.get_mut().observer_mut().next(item);
+    Ok(())
+  }
+
+  fn poll_flush(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<Result<(), Infallible>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn poll_close(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<Result<(), Infallible>> {
+    self.get_mut().observer_mut().complete();
+    Poll::Ready(Ok(()))
+  }
+}
+
+trait ObserverMut<Item, Err> {
+  type Observer: Observer<Item, Err>;
+  fn observer_mut(&mut self) -> &mut Self::Observer;
+}
+
+impl<O, Item, Err> ObserverMut<Item, Err> for ObserverSink<O, Err>
+where
+  O: Observer<Item, Err>,
+{
+  type Observer = O;
+  #[inline]
+  fn observer_mut(&mut self) -> &mut O { &mut self.observer }
+}
+
+impl<O, Item, Err> Sink<Item> for ObserverSink<O, Err>
+where
+  O: Observer<Item, Err>,
+{
+  sink_impl!(Item);
+}
+
+impl<'a, Item: Clone, Err: Clone> ObserverMut<Item, Err>
+  for LocalSubject<'a, Item, Err>
+{
+  type Observer = Self;
+  #[inline]
+  fn observer_mut(&mut self) -> &mut Self { self }
+}
+
+impl<'a, Item: Clone, Err: Clone> Sink<Item> for LocalSubject<'a, Item, Err> {
+  sink_impl!(Item);
+}
+
+impl<Item: Clone, Err: Clone> ObserverMut<Item, Err>
+  for SharedSubject<Item, Err>
+{
+  type Observer = Self;
+  #[inline]
+  fn observer_mut(&mut self) -> &mut Self { self }
+}
+
+impl<Item: Clone, Err: Clone> Sink<Item> for SharedSubject<Item, Err> {
+  sink_impl!(Item);
+}
+
+/// An Observer sending the values it observes into a [`Sink`].
+///
+/// The values are buffered, and sent into the sink by a task on an executor
+/// whenever the sink is ready to receive one. When the buffer is full, `next`
+/// blocks the thread emitting the values until the sink has taken one, so a
+/// slow sink slows the Observable down instead of losing values. Waiting
+/// doesn't involve an executor, so the Observable may emit on an executor's
+/// thread, such as with `observe_on(Schedulers::ThreadPool)`. But the task
+/// sending into the sink needs a thread of its own then: `spawner` must not
+/// run its tasks on the thread the Observable emits on, or a full buffer never
+/// drains.
+///
+/// The sink is closed once the Observable completes or emits an error. A sink
+/// can't receive an error, so the error itself is dropped. If the sink fails,
+/// all further values are dropped.
+///
+/// # Example
+///
+/// ```
+/// use futures::{channel::mpsc, executor::{block_on, ThreadPool}, StreamExt};
+/// use rxrust::{prelude::*, sink::SinkObserver};
+///
+/// let (tx, rx) = mpsc::channel(1);
+/// observable::from_iter(0..3)
+///   .to_shared()
+///   .actual_subscribe(Subscriber::shared(
+///     SinkObserver::new(tx, 8, ThreadPool::new().unwrap())
+///   ));
+/// let values: Vec<i32> = block_on(rx.collect());
+/// assert_eq!(values, vec![0, 1, 2]);
+/// ```
+#[derive(Clone)]
+pub struct SinkObserver<Item> {
+  buffer: Arc<SinkBuffer<Item>>,
+}
+
+struct SinkBuffer<Item> {
+  state: Mutex<BufferState<Item>>,
+  // Signalled whenever the task took a value, or ended.
+  taken: Condvar,
+  capacity: usize,
+}
+
+struct BufferState<Item> {
+  values: VecDeque<Item>,
+  // No more values are coming.
+  closed: bool,
+  // The task sending into the sink ended, no more values are taken.
+  dropped: bool,
+  waker: Option<Waker>,
+}
+
+impl<Item> SinkBuffer<Item> {
+  fn close(&self) {
+    let mut state = self.state.lock().unwrap();
+    state.closed = true;
+    if let Some(waker) = state.waker.take() {
+      waker.wake();
+    }
+  }
+}
+
+/// The values of a [`SinkBuffer`] as a stream, which the task forwards into
+/// the sink.
+struct BufferStream<Item>(Arc<SinkBuffer<Item>>);
+
+impl<Item> Stream for BufferStream<Item> {
+  type Item = Item;
+
+  fn poll_next(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<Option<Item>> {
+    let mut state = self.0.state.lock().unwrap();
+    match state.values.pop_front() {
+      Some(value) => {
+        self.0.taken.notify_all();
+        Poll::Ready(Some(value))
+      }
+      None if state.closed => Poll::Ready(None),
+      None => {
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+      }
+    }
+  }
+}
+
+impl<Item> Drop for BufferStream<Item> {
+  fn drop(&mut self) {
+    let mut state = self.0.state.lock().unwrap();
+    state.dropped = true;
+    state.values.clear();
+    self.0.taken.notify_all();
+  }
+}
+
+impl<Item> SinkObserver<Item> {
+  /// Creates an Observer sending into `sink` on `spawner`, buffering up to
+  /// `capacity` values for it, but at least one.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the task sending into `sink` can't be spawned.
+  pub fn new<Si, Sp>(sink: Si, capacity: usize, mut spawner: Sp) -> Self
+  where
+    Si: Sink<Item> + Send + 'static,
+    Item: Send + 'static,
+    Sp: Spawn,
+  {
+    let buffer = Arc::new(SinkBuffer {
+      state: Mutex::new(BufferState {
+        values: VecDeque::new(),
+        closed: false,
+        dropped: false,
+        waker: None,
+      }),
+      taken: Condvar::new(),
+      capacity: capacity.max(1),
+    });
+    let f = BufferStream(buffer.clone())
+      .map(Ok)
+      .forward(sink)
+      .map(|_| ());
+    spawner.spawn(f).expect("spawn future for a sink failed");
+    SinkObserver { buffer }
+  }
+}
+
+impl<Item, Err> Observer<Item, Err> for SinkObserver<Item> {
+  fn next(&mut self, value: Item) {
+    let buffer = &self.buffer;
+    let mut state = buffer.state.lock().unwrap();
+    while state.values.len() >= buffer.capacity && !state.dropped {
+      state = buffer.taken.wait(state).unwrap();
+    }
+    // If the sink failed, the value is dropped.
+    if !state.dropped && !state.closed {
+      state.values.push_back(value);
+      if let Some(waker) = state.waker.take() {
+        waker.wake();
+      }
+    }
+  }
+
+  /// Closes the sink, dropping `err`.
+  fn error(&mut self, _err: Err) { self.buffer.close() }
+
+  fn complete(&mut self) { self.buffer.close() }
+}
+
+#[cfg(test)]
+mod test {
+  use super::{ObserverSink, SinkObserver};
+  use crate::observable::to_future::EmptyOrErr;
+  use crate::prelude::*;
+  use futures::channel::mpsc;
+  use futures::executor::{ThreadPool, block_on};
+  use futures::prelude::*;
+  use std::cell::RefCell;
+  use std::sync::Arc;
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  #[test]
+  fn subject_as_sink() {
+    let values = RefCell::new(vec![]);
+    let completed = RefCell::new(false);
+    {
+      let mut subject = LocalSubject::new();
+      subject.clone().subscribe_complete(
+        |v| values.borrow_mut().push(v),
+        || *completed.borrow_mut() = true,
+      );
+      block_on(subject.send(1)).unwrap();
+      block_on(stream::iter(vec![Ok(2), Ok(3)]).forward(&mut subject)).unwrap();
+    }
+    assert_eq!(*values.borrow(), vec![1, 2, 3]);
+    // `forward` closes the sink.
+    assert!(*completed.borrow());
+  }
+
+  #[test]
+  fn shared_subject_as_sink() {
+    let subject = SharedSubject::new();
+    let mut last = subject.clone().last_value();
+    // Polling once subscribes to the subject before it is fed.
+    assert_eq!((&mut last).now_or_never(), None);
+    block_on(stream::iter(0..4).map(Ok).forward(subject)).unwrap();
+    assert_eq!(block_on(last), Ok::<_, EmptyOrErr<()>>(3));
+  }
+
+  #[test]
+  fn observer_as_sink() {
+    let mut sum = 0;
+    let mut completed = false;
+    {
+      let observer = crate::observable::ObserverComp::new(
+        |v: i32| sum += v,
+        || completed = true,
+      );
+      let mut sink = ObserverSink::new(observer);
+      block_on(sink.send_all(&mut stream::iter(vec![1, 2, 3]))).unwrap();
+      block_on(sink.close()).unwrap();
+    }
+    assert_eq!(sum, 6);
+    assert!(completed);
+  }
+
+  #[test]
+  fn sink_observer_backpressure() {
+    // Nobody reads from the sink yet, so the buffers fill up.
+    let (tx, rx) = mpsc::channel(0);
+    let emitted = Arc::new(AtomicUsize::new(0));
+    let c_emitted = emitted.clone();
+    let producer = std::thread::spawn(move || {
+      let observer = SinkObserver::new(tx, 1, ThreadPool::new().unwrap());
+      observable::from_iter(0..100)
+        .tap_next(move |_| {
+          c_emitted.fetch_add(1, Ordering::SeqCst);
+        })
+        .to_shared()
+        .actual_subscribe(Subscriber::shared(observer));
+    });
+
+    let mut rx = rx;
+    let mut received = 0;
+    while let Some(v) = block_on(rx.next()) {
+      assert_eq!(v, received);
+      received += 1;
+      // Besides the value waiting in `next`, at most one value each is in
+      // the buffer, in the forwarding task and in the channel.
+      assert!(emitted.load(Ordering::SeqCst) <= received as usize + 4);
+    }
+    producer.join().unwrap();
+    assert_eq!(received, 100);
+  }
+
+  #[test]
+  fn sink_observer_on_thread_pool() {
+    let (tx, rx) = mpsc::channel(0);
+    let mut subject: SharedSubject<i32, ()> = SharedSubject::new();
+    subject
+      .clone()
+      .to_shared()
+      .observe_on(Schedulers::ThreadPool)
+      .to_shared()
+      .actual_subscribe(Subscriber::shared(SinkObserver::new(
+        tx,
+        1,
+        ThreadPool::new().unwrap(),
+      )));
+    // Far more than fit into the buffer and the channel.
+    for i in 0..10 {
+      subject.next(i);
+    }
+    // The pool may deliver the values out of order.
+    let mut values: Vec<i32> = block_on(rx.take(10).collect());
+    values.sort();
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn sink_observer_closes_on_error() {
+    let (tx, rx) = mpsc::unbounded();
+    let mut subject = SharedSubject::new();
+    subject
+      .clone()
+      .actual_subscribe(Subscriber::shared(SinkObserver::new(
+        tx,
+        1,
+        ThreadPool::new().unwrap(),
+      )));
+    subject.next(1);
+    subject.error("oops");
+    let values: Vec<i32> = block_on(rx.collect());
+    assert_eq!(values, vec![1]);
+  }
+}